@@ -0,0 +1,73 @@
+// cargo run --example hit_log --target $(rustc -vV | sed -n 's|host: ||p') -- autosplitter.log > hits.csv
+
+extern crate asr;
+#[cfg(not(target_os = "unknown"))]
+extern crate serde_json;
+extern crate silksong_autosplit_wasm;
+extern crate std;
+
+#[cfg(not(target_os = "unknown"))]
+use silksong_autosplit_wasm::hits::HIT_LOG_PREFIX;
+#[cfg(not(target_os = "unknown"))]
+use std::{
+    env, fs,
+    io::{self, BufWriter, Write},
+};
+
+/// Reads the hit log records from a log, and writes them as CSV,
+/// one row per segment of each run
+fn main() -> std::io::Result<()> {
+    #[cfg(not(target_os = "unknown"))]
+    {
+        let log = match env::args().nth(1) {
+            Some(path) => fs::read_to_string(path)?,
+            None => io::read_to_string(io::stdin())?,
+        };
+
+        let mut writer = BufWriter::new(io::stdout().lock());
+        writeln!(
+            &mut writer,
+            "record,event,splits,masks_lost,segment,name,hits,enemy,hazard,deaths,cumulative,comparison"
+        )?;
+        let records = log.lines().filter_map(|line| {
+            let (_, record) = line.split_once(HIT_LOG_PREFIX)?;
+            serde_json::from_str::<serde_json::Value>(record).ok()
+        });
+        for (n, record) in records.enumerate() {
+            let segments = record["segments"].as_array().cloned().unwrap_or_default();
+            for (i, s) in segments.iter().enumerate() {
+                writeln!(
+                    &mut writer,
+                    "{},{},{},{},{},{},{},{},{},{},{},{}",
+                    n,
+                    csv_field(&record["event"]),
+                    csv_field(&record["splits"]),
+                    csv_field(&record["masks_lost"]),
+                    i,
+                    csv_field(&s["name"]),
+                    csv_field(&s["hits"]),
+                    csv_field(&s["enemy"]),
+                    csv_field(&s["hazard"]),
+                    csv_field(&s["deaths"]),
+                    csv_field(&s["cumulative"]),
+                    csv_field(&s["comparison"])
+                )?;
+            }
+        }
+        writer.flush()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(not(target_os = "unknown"))]
+fn csv_field(v: &serde_json::Value) -> String {
+    match v {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(s) if s.contains([',', '"', '\n']) => {
+            format!("\"{}\"", s.replace('"', "\"\""))
+        }
+        serde_json::Value::String(s) => s.clone(),
+        v => v.to_string(),
+    }
+}
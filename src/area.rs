@@ -0,0 +1,119 @@
+use alloc::string::{String, ToString};
+
+use crate::silksong_memory::{GameManagerPointers, Memory, PlayerDataPointers};
+
+// --------------------------------------------------------
+
+/// The map zone, such as `MOSS_CAVE`, from the GameManager's mapZoneString
+pub fn map_zone(mem: &Memory, gm: &GameManagerPointers) -> Option<String> {
+    mem.read_string(&gm.map_zone_string)
+        .filter(|z| !z.is_empty())
+}
+
+/// A human-readable name for the current area,
+/// from the map zone, or from currentArea when there is no map zone.
+/// Map zones use their in-game name when it differs from the name in the code
+pub fn area_name(
+    mem: &Memory,
+    gm: &GameManagerPointers,
+    pd: &PlayerDataPointers,
+) -> Option<String> {
+    if let Some(zone) = map_zone(mem, gm) {
+        return Some(zone_name(&zone).map_or_else(|| title_case(&zone), ToString::to_string));
+    }
+    mem.read_string(&pd.current_area).filter(|a| !a.is_empty())
+}
+
+/// The in-game name of a map zone whose name in the code is different
+fn zone_name(zone: &str) -> Option<&'static str> {
+    match zone {
+        "ARBORIUM" => Some("Memorium"),
+        "BELLTOWN" => Some("Bellhart"),
+        "BONE_EAST" => Some("Far Fields"),
+        "BONETOWN" => Some("Bone Bottom"),
+        "CLOVER" => Some("Verdania"),
+        "COG_CORE" => Some("Cogwork Core"),
+        "CORAL_CAVERNS" => Some("Sands of Karak"),
+        "CRADLE" => Some("The Cradle"),
+        "CRAWLSPACE" => Some("Wormways"),
+        "DOCKS" => Some("Deep Docks"),
+        "DUSTPENS" => Some("Sinner's Road"),
+        "HALFWAY_HOUSE" => Some("Halfway Home"),
+        "HANG" => Some("High Halls"),
+        "HUNTERS_NEST" => Some("Hunter's March"),
+        "JUDGE_STEPS" => Some("Blasted Steps"),
+        "LIBRARY" => Some("Whispering Vaults"),
+        "MOSS_CAVE" => Some("Moss Grotto"),
+        "PATH_OF_BONE" => Some("The Marrow"),
+        "PEAK" => Some("Mount Fay"),
+        "SHELLWOOD_THICKET" => Some("Shellwood"),
+        "SWAMP" => Some("Bilewater"),
+        "UNDERSTORE" => Some("Underworks"),
+        "WISP" => Some("Wisp Thicket"),
+        _ => None,
+    }
+}
+
+/// Turns `GREYMOOR` into `Greymoor`,
+/// for the map zones without an entry in `zone_name`
+pub fn title_case(s: &str) -> String {
+    let mut r = String::with_capacity(s.len());
+    for word in s.split('_').filter(|w| !w.is_empty()) {
+        if !r.is_empty() {
+            r.push(' ');
+        }
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            r.extend(first.to_uppercase());
+            r.push_str(&chars.as_str().to_lowercase());
+        }
+    }
+    r
+}
+
+/// Area tracking for the area variable
+#[derive(Debug, Default)]
+pub struct AreaStore {
+    scene_name: String,
+    pending: bool,
+    area: String,
+}
+
+impl AreaStore {
+    /// Clears the area variable, if it was set
+    pub fn clear(&mut self) {
+        if !self.area.is_empty() {
+            asr::timer::set_variable("area", crate::DASH);
+        }
+        *self = AreaStore::default();
+    }
+
+    /// Reads the area again after a transition into `scene_name`,
+    /// until the game is playing in the new scene.
+    /// Returns the new area when it changed
+    pub fn update(
+        &mut self,
+        scene_name: &str,
+        playing: bool,
+        mem: &Memory,
+        gm: &GameManagerPointers,
+        pd: &PlayerDataPointers,
+    ) -> Option<&str> {
+        if scene_name != self.scene_name {
+            self.scene_name = scene_name.to_string();
+            self.pending = true;
+        }
+        if !self.pending {
+            return None;
+        }
+        if playing {
+            self.pending = false;
+        }
+        let area = area_name(mem, gm, pd).unwrap_or_default();
+        if area == self.area {
+            return None;
+        }
+        self.area = area;
+        Some(&self.area)
+    }
+}
@@ -0,0 +1,196 @@
+use alloc::string::String;
+use asr::settings::Gui;
+use ugly_widget::{
+    radio_button::{options_str, RadioButtonOptions},
+    store::StoreWidget,
+};
+
+use crate::silksong_memory::{pd_fields, Memory, PlayerDataPointers};
+
+// --------------------------------------------------------
+
+#[derive(Clone, Debug, Default, Eq, Gui, Ord, PartialEq, PartialOrd, RadioButtonOptions)]
+pub enum PracticeBoss {
+    /// Moss Mother
+    #[default]
+    MossMother,
+    /// Bell Beast
+    BellBeast,
+    /// Lace 1
+    Lace1,
+    /// Fourth Chorus
+    FourthChorus,
+    /// Forebrothers
+    Forebrothers,
+    /// Moorwing
+    Moorwing,
+    /// Widow
+    Widow,
+    /// Phantom
+    Phantom,
+    /// Conchflies 1
+    Conchflies1,
+    /// Last Judge
+    LastJudge,
+    /// Trobbio
+    Trobbio,
+    /// Cogwork Dancers
+    CogworkDancers,
+    /// Lace 2
+    Lace2,
+}
+
+impl StoreWidget for PracticeBoss {
+    fn insert_into(&self, settings_map: &asr::settings::Map, key: &str) -> bool {
+        let new_s = options_str(self);
+        if settings_map
+            .get(key)
+            .is_some_and(|old_v| old_v.get_string().is_some_and(|old_s| old_s == new_s))
+        {
+            return false;
+        }
+        settings_map.insert(key, new_s);
+        true
+    }
+}
+
+impl PracticeBoss {
+    /// The key that the boss's attempt stats are stored under
+    pub fn key(&self) -> &str {
+        options_str(self)
+    }
+
+    /// The names of the encountered and defeated flags, for the diagnostics
+    pub fn flag_fields(&self) -> [&'static str; 2] {
+        match self {
+            PracticeBoss::MossMother => [
+                pd_fields::encountered_moss_mother,
+                pd_fields::defeated_moss_mother,
+            ],
+            PracticeBoss::BellBeast => [
+                pd_fields::encountered_bell_beast,
+                pd_fields::defeated_bell_beast,
+            ],
+            PracticeBoss::Lace1 => [pd_fields::encountered_lace1, pd_fields::defeated_lace1],
+            PracticeBoss::FourthChorus => [
+                pd_fields::encountered_song_golem,
+                pd_fields::defeated_song_golem,
+            ],
+            PracticeBoss::Forebrothers => [
+                pd_fields::encountered_dock_foremen,
+                pd_fields::defeated_dock_foremen,
+            ],
+            PracticeBoss::Moorwing => [
+                pd_fields::encountered_vampire_gnat_boss,
+                pd_fields::defeated_vampire_gnat_boss,
+            ],
+            PracticeBoss::Widow => [pd_fields::encountered_spinner, pd_fields::spinner_defeated],
+            PracticeBoss::Phantom => [pd_fields::encountered_phantom, pd_fields::defeated_phantom],
+            PracticeBoss::Conchflies1 => [
+                pd_fields::encountered_coral_drillers,
+                pd_fields::defeated_coral_drillers,
+            ],
+            PracticeBoss::LastJudge => [
+                pd_fields::encountered_last_judge,
+                pd_fields::defeated_last_judge,
+            ],
+            PracticeBoss::Trobbio => [pd_fields::encountered_trobbio, pd_fields::defeated_trobbio],
+            PracticeBoss::CogworkDancers => [
+                pd_fields::encountered_cogwork_dancers,
+                pd_fields::defeated_cogwork_dancers,
+            ],
+            PracticeBoss::Lace2 => [
+                pd_fields::encountered_lace_tower,
+                pd_fields::defeated_lace_tower,
+            ],
+        }
+    }
+
+    pub fn encountered(&self, mem: &Memory, pd: &PlayerDataPointers) -> Option<bool> {
+        let p = match self {
+            PracticeBoss::MossMother => &pd.encountered_moss_mother,
+            PracticeBoss::BellBeast => &pd.encountered_bell_beast,
+            PracticeBoss::Lace1 => &pd.encountered_lace1,
+            PracticeBoss::FourthChorus => &pd.encountered_song_golem,
+            PracticeBoss::Forebrothers => &pd.encountered_dock_foremen,
+            PracticeBoss::Moorwing => &pd.encountered_vampire_gnat_boss,
+            PracticeBoss::Widow => &pd.encountered_spinner,
+            PracticeBoss::Phantom => &pd.encountered_phantom,
+            PracticeBoss::Conchflies1 => &pd.encountered_coral_drillers,
+            PracticeBoss::LastJudge => &pd.encountered_last_judge,
+            PracticeBoss::Trobbio => &pd.encountered_trobbio,
+            PracticeBoss::CogworkDancers => &pd.encountered_cogwork_dancers,
+            PracticeBoss::Lace2 => &pd.encountered_lace_tower,
+        };
+        mem.deref(p).ok()
+    }
+
+    pub fn defeated(&self, mem: &Memory, pd: &PlayerDataPointers) -> Option<bool> {
+        let p = match self {
+            PracticeBoss::MossMother => &pd.defeated_moss_mother,
+            PracticeBoss::BellBeast => &pd.defeated_bell_beast,
+            PracticeBoss::Lace1 => &pd.defeated_lace1,
+            PracticeBoss::FourthChorus => &pd.defeated_song_golem,
+            PracticeBoss::Forebrothers => &pd.defeated_dock_foremen,
+            PracticeBoss::Moorwing => &pd.defeated_vampire_gnat_boss,
+            PracticeBoss::Widow => &pd.spinner_defeated,
+            PracticeBoss::Phantom => &pd.defeated_phantom,
+            PracticeBoss::Conchflies1 => &pd.defeated_coral_drillers,
+            PracticeBoss::LastJudge => &pd.defeated_last_judge,
+            PracticeBoss::Trobbio => &pd.defeated_trobbio,
+            PracticeBoss::CogworkDancers => &pd.defeated_cogwork_dancers,
+            PracticeBoss::Lace2 => &pd.defeated_lace_tower,
+        };
+        mem.deref(p).ok()
+    }
+}
+
+/// The arena and attempt stats of a practice boss, persisted in the settings map
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BossRecord {
+    /// The scene the boss was first encountered or defeated in, or that was set as its arena
+    pub arena: Option<String>,
+    pub attempts: i64,
+    pub kills: i64,
+}
+
+impl BossRecord {
+    pub fn set_variables(&self) {
+        asr::timer::set_variable_int("boss attempts", self.attempts);
+        asr::timer::set_variable_int("boss kills", self.kills);
+    }
+}
+
+/// Edge detection on the flags of the practice boss
+#[derive(Default)]
+pub struct BossFlags {
+    boss: Option<PracticeBoss>,
+    encountered: Option<bool>,
+    defeated: Option<bool>,
+}
+
+impl BossFlags {
+    /// Reads the flags of `boss`, returning whether it was just encountered
+    /// and whether it was just defeated.
+    /// Switching to a different boss forgets the previous flags.
+    pub fn update(
+        &mut self,
+        boss: &PracticeBoss,
+        mem: &Memory,
+        pd: &PlayerDataPointers,
+    ) -> (bool, bool) {
+        if self.boss.as_ref() != Some(boss) {
+            *self = BossFlags {
+                boss: Some(boss.clone()),
+                ..Default::default()
+            };
+        }
+        let encountered = boss.encountered(mem, pd);
+        let defeated = boss.defeated(mem, pd);
+        let just_encountered = self.encountered == Some(false) && encountered == Some(true);
+        let just_defeated = self.defeated == Some(false) && defeated == Some(true);
+        self.encountered = encountered;
+        self.defeated = defeated;
+        (just_encountered, just_defeated)
+    }
+}
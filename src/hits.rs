@@ -0,0 +1,473 @@
+use alloc::{format, string::String, vec, vec::Vec};
+use asr::settings::Gui;
+use core::{
+    cmp,
+    fmt::Write,
+    iter::Sum,
+    ops::{Add, AddAssign},
+};
+use ugly_widget::{
+    radio_button::{options_str, RadioButtonOptions},
+    store::StoreWidget,
+};
+
+use crate::silksong_memory::{Memory, PlayerDataPointers};
+
+// --------------------------------------------------------
+
+#[derive(Clone, Debug, Default, Eq, Gui, Ord, PartialEq, PartialOrd, RadioButtonOptions)]
+pub enum HitMode {
+    /// Recoil
+    ///
+    /// Counts a hit each time the hero recoils, touches a hazard, or dies
+    #[default]
+    Recoil,
+    /// Damage
+    ///
+    /// Counts a hit each time health or blue health goes down,
+    /// and counts the masks lost
+    Damage,
+}
+
+impl StoreWidget for HitMode {
+    fn insert_into(&self, settings_map: &asr::settings::Map, key: &str) -> bool {
+        let new_s = options_str(self);
+        if settings_map
+            .get(key)
+            .is_some_and(|old_v| old_v.get_string().is_some_and(|old_s| old_s == new_s))
+        {
+            return false;
+        }
+        settings_map.insert(key, new_s);
+        true
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, Gui, Ord, PartialEq, PartialOrd, RadioButtonOptions)]
+pub enum HitlessMode {
+    /// Off
+    ///
+    /// Hits don't affect the run
+    #[default]
+    Off,
+    /// Reset
+    ///
+    /// Resets the timer on the first hit
+    Reset,
+    /// Failed Split
+    ///
+    /// Skips to the Hitless Failed split and splits it on the first hit
+    FailedSplit,
+    /// Variable
+    ///
+    /// Sets the hitless variable to FAILED on the first hit
+    Variable,
+}
+
+impl StoreWidget for HitlessMode {
+    fn insert_into(&self, settings_map: &asr::settings::Map, key: &str) -> bool {
+        let new_s = options_str(self);
+        if settings_map
+            .get(key)
+            .is_some_and(|old_v| old_v.get_string().is_some_and(|old_s| old_s == new_s))
+        {
+            return false;
+        }
+        settings_map.insert(key, new_s);
+        true
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, Gui, Ord, PartialEq, PartialOrd, RadioButtonOptions)]
+pub enum HitlessSegment {
+    /// Hitless
+    ///
+    /// A hit in this segment fails the run
+    #[default]
+    Hitless,
+    /// Exempt
+    ///
+    /// Hits in this segment are counted, but don't fail the run
+    Exempt,
+}
+
+impl StoreWidget for HitlessSegment {
+    fn insert_into(&self, settings_map: &asr::settings::Map, key: &str) -> bool {
+        let new_s = options_str(self);
+        if settings_map
+            .get(key)
+            .is_some_and(|old_v| old_v.get_string().is_some_and(|old_s| old_s == new_s))
+        {
+            return false;
+        }
+        settings_map.insert(key, new_s);
+        true
+    }
+}
+
+// --------------------------------------------------------
+
+/// Where a hit came from
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HitCause {
+    /// Recoil from an enemy or an enemy's attack
+    Enemy,
+    /// A hazard, such as spikes
+    Hazard,
+    /// Health reaching 0
+    Death,
+}
+
+/// Hit counts, separated by cause
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct HitCounts {
+    pub enemy: i64,
+    pub hazard: i64,
+    pub deaths: i64,
+}
+
+impl HitCounts {
+    pub fn add_hit(&mut self, cause: HitCause) {
+        match cause {
+            HitCause::Enemy => self.enemy += 1,
+            HitCause::Hazard => self.hazard += 1,
+            HitCause::Death => self.deaths += 1,
+        }
+    }
+
+    pub fn total(&self) -> i64 {
+        self.enemy + self.hazard + self.deaths
+    }
+
+    /// The minimum of each cause separately
+    pub fn min(self, other: HitCounts) -> HitCounts {
+        HitCounts {
+            enemy: cmp::min(self.enemy, other.enemy),
+            hazard: cmp::min(self.hazard, other.hazard),
+            deaths: cmp::min(self.deaths, other.deaths),
+        }
+    }
+}
+
+impl Add for HitCounts {
+    type Output = HitCounts;
+    fn add(self, other: HitCounts) -> HitCounts {
+        HitCounts {
+            enemy: self.enemy + other.enemy,
+            hazard: self.hazard + other.hazard,
+            deaths: self.deaths + other.deaths,
+        }
+    }
+}
+
+impl AddAssign for HitCounts {
+    fn add_assign(&mut self, other: HitCounts) {
+        *self = *self + other;
+    }
+}
+
+impl Sum for HitCounts {
+    fn sum<I: Iterator<Item = HitCounts>>(iter: I) -> HitCounts {
+        iter.fold(HitCounts::default(), Add::add)
+    }
+}
+
+// --------------------------------------------------------
+
+/// The health values read for damage-based hit counting
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DamageSnapshot {
+    pub health: i32,
+    pub health_blue: i32,
+    pub damaged_blue: bool,
+    pub damaged_purple: bool,
+}
+
+impl DamageSnapshot {
+    /// Reads a snapshot, with the health from the tick snapshot
+    pub fn read(
+        mem: &Memory,
+        pd: &PlayerDataPointers,
+        health: Option<i32>,
+    ) -> Option<DamageSnapshot> {
+        Some(DamageSnapshot {
+            health: health?,
+            health_blue: mem.deref(&pd.health_blue).unwrap_or_default(),
+            damaged_blue: mem.deref(&pd.damaged_blue).unwrap_or_default(),
+            damaged_purple: mem.deref(&pd.damaged_purple).unwrap_or_default(),
+        })
+    }
+}
+
+/// The masks lost between two snapshots, if the hero took damage.
+///
+/// Damage can also be taken without losing masks,
+/// when the damagedBlue or damagedPurple flags are set.
+pub fn damage_taken(last: &DamageSnapshot, now: &DamageSnapshot) -> Option<i64> {
+    let masks = cmp::max(0, last.health - now.health) as i64
+        + cmp::max(0, last.health_blue - now.health_blue) as i64;
+    let flagged =
+        (!last.damaged_blue && now.damaged_blue) || (!last.damaged_purple && now.damaged_purple);
+    (0 < masks || flagged).then_some(masks)
+}
+
+// --------------------------------------------------------
+
+/// Updates the best hits for each segment with the segment hits from a run.
+///
+/// A segment in a run is `None` when it wasn't timed on its own,
+/// such as a skipped segment or the segment after a skip.
+pub fn update_best_segment_hits(best: &mut Vec<Option<i64>>, run: &[Option<i64>]) {
+    if best.len() < run.len() {
+        best.resize(run.len(), None);
+    }
+    for (b, r) in best.iter_mut().zip(run) {
+        *b = match (*b, *r) {
+            (Some(b), Some(r)) => Some(cmp::min(b, r)),
+            (b, r) => b.or(r),
+        };
+    }
+}
+
+/// The sum of best hits, only when every segment of the `splits_len` splits has a best
+pub fn sum_of_best_hits(best: &[Option<i64>], splits_len: usize) -> Option<i64> {
+    if best.is_empty() || best.len() + 1 != splits_len {
+        return None;
+    }
+    best.iter().copied().sum()
+}
+
+// --------------------------------------------------------
+
+/// Undoes splits back to split index `new_i`,
+/// moving the hits from the undone segments into segment `new_i`
+pub fn undo_to(
+    segments_splitted: &mut Vec<bool>,
+    segment_hits: &mut Vec<HitCounts>,
+    cumulative_hits: &mut Vec<HitCounts>,
+    new_i: usize,
+) {
+    if segment_hits.len() <= new_i {
+        segment_hits.resize(new_i + 1, HitCounts::default());
+    }
+    let undone = segment_hits.drain((new_i + 1)..).sum::<HitCounts>();
+    segment_hits[new_i] += undone;
+    if new_i < cumulative_hits.len() {
+        let mut i = new_i;
+        // go back through skipped splits
+        while 1 <= i && segments_splitted.get(i - 1) != Some(&true) {
+            i -= 1;
+        }
+        // segment [i - 1] was not skipped, but segment [i] was skipped or undone,
+        // so remove cumulative_hits from there on
+        cumulative_hits.truncate(i);
+    }
+    segments_splitted.truncate(new_i);
+}
+
+/// The split index once the timer ended.
+///
+/// `timer_index` is the timer's split index, which is only known with the `unstable` feature.
+/// Without it, the final split was made in the timer,
+/// unless `split_made` says the auto splitter made it and already counted it.
+pub fn end_split_index(
+    split_index: Option<u64>,
+    timer_index: Option<u64>,
+    split_made: bool,
+) -> u64 {
+    let i = split_index.unwrap_or_default();
+    match timer_index {
+        Some(t) => t,
+        None if split_made => i,
+        None => i + 1,
+    }
+}
+
+/// Counts the final split at `end_i` as splitted,
+/// and the segments before it that the auto splitter didn't see as skipped
+pub fn end_segments(segments_splitted: &mut Vec<bool>, end_i: usize) {
+    if segments_splitted.len() < end_i {
+        segments_splitted.resize(end_i - 1, false);
+        segments_splitted.push(true);
+    }
+}
+
+/// The split index to go back to when the final split at `split_index` is undone
+pub fn undo_end_split_index(split_index: Option<u64>, timer_index: Option<u64>) -> u64 {
+    timer_index.unwrap_or_else(|| split_index.unwrap_or_default().saturating_sub(1))
+}
+
+// --------------------------------------------------------
+
+/// A fingerprint of a list of splits, to tell which splits comparisons were recorded for
+pub fn splits_fingerprint(splits: &[String]) -> String {
+    // FNV-1a, with a 0 byte after each split
+    let mut h: u64 = 0xcbf29ce484222325;
+    for b in splits.iter().flat_map(|s| s.bytes().chain([0])) {
+        h ^= b as u64;
+        h = h.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", h)
+}
+
+/// For each of the new splits, the index of the same split in the old splits.
+///
+/// Splits that were inserted are `None`.
+/// When the first split changed, all of them are `None`, since the runs start differently.
+pub fn remap_splits(old: &[String], new: &[String]) -> Vec<Option<usize>> {
+    if old.first() != new.first() {
+        return new.iter().map(|_| None).collect();
+    }
+    // the longest common subsequence keeps the splits that stayed in order
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                cmp::max(lcs[i + 1][j], lcs[i][j + 1])
+            };
+        }
+    }
+    let mut map = vec![None; new.len()];
+    let mut taken = vec![false; old.len()];
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            map[j] = Some(i);
+            taken[i] = true;
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    // the splits left over on both sides were moved
+    for (j, n) in new.iter().enumerate() {
+        if map[j].is_some() {
+            continue;
+        }
+        if let Some(k) = (0..old.len()).find(|k| !taken[*k] && old[*k] == *n) {
+            map[j] = Some(k);
+            taken[k] = true;
+        }
+    }
+    map
+}
+
+/// Remaps cumulative comparisons, where comparison `i` is at the end of split `i + 1`.
+///
+/// A comparison is only kept when the splits before it that are still there are the same,
+/// since inserted and removed splits don't change the hits up to it, but moved splits do.
+pub fn remap_cumulative<T: Copy>(
+    old_comparisons: &[Option<T>],
+    map: &[Option<usize>],
+) -> Vec<Option<T>> {
+    (1..map.len())
+        .map(|j| {
+            let k = map[j].filter(|k| 1 <= *k)?;
+            let kept_before = map[..=j].iter().flatten();
+            if kept_before.clone().any(|m| k < *m)
+                || kept_before.count() != map.iter().flatten().filter(|m| **m <= k).count()
+            {
+                return None;
+            }
+            old_comparisons.get(k - 1).copied().flatten()
+        })
+        .collect()
+}
+
+/// Remaps segment comparisons, where segment `i` is from split `i` to split `i + 1`.
+///
+/// A segment is only kept when both of its splits are still next to each other.
+pub fn remap_segments(old_segments: &[Option<i64>], map: &[Option<usize>]) -> Vec<Option<i64>> {
+    map.windows(2)
+        .map(|w| match (w[0], w[1]) {
+            (Some(a), Some(b)) if a + 1 == b => old_segments.get(a).copied().flatten(),
+            _ => None,
+        })
+        .collect()
+}
+
+// --------------------------------------------------------
+
+/// The prefix of hit log records in the log
+pub const HIT_LOG_PREFIX: &str = "hit log: ";
+
+/// One segment in a hit log record
+pub struct HitLogSegment<'a> {
+    pub name: &'a str,
+    pub hits: HitCounts,
+    pub cumulative: Option<i64>,
+    pub comparison: Option<i64>,
+}
+
+/// A single-line JSON record of the hits in a run, `event` being reset or finish.
+/// `masks_lost` is only counted when counting hits from damage
+pub fn hit_log_record(
+    event: &str,
+    fingerprint: &str,
+    segments: &[HitLogSegment],
+    masks_lost: Option<i64>,
+    death_scenes: &[String],
+) -> String {
+    let mut r = String::new();
+    let _ = write!(
+        r,
+        "{{\"event\":{},\"splits\":{},\"masks_lost\":{},\"segments\":[",
+        json_string(event),
+        json_string(fingerprint),
+        json_option(masks_lost)
+    );
+    for (i, s) in segments.iter().enumerate() {
+        if 0 < i {
+            r.push(',');
+        }
+        let _ = write!(
+            r,
+            "{{\"name\":{},\"hits\":{},\"enemy\":{},\"hazard\":{},\"deaths\":{},\"cumulative\":{},\"comparison\":{}}}",
+            json_string(s.name),
+            s.hits.total(),
+            s.hits.enemy,
+            s.hits.hazard,
+            s.hits.deaths,
+            json_option(s.cumulative),
+            json_option(s.comparison)
+        );
+    }
+    r.push_str("],\"death_scenes\":[");
+    for (i, s) in death_scenes.iter().enumerate() {
+        if 0 < i {
+            r.push(',');
+        }
+        r.push_str(&json_string(s));
+    }
+    r.push_str("]}");
+    r
+}
+
+fn json_string(s: &str) -> String {
+    let mut r = String::with_capacity(s.len() + 2);
+    r.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => r.push_str("\\\""),
+            '\\' => r.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(r, "\\u{:04x}", c as u32);
+            }
+            c => r.push(c),
+        }
+    }
+    r.push('"');
+    r
+}
+
+fn json_option(o: Option<i64>) -> String {
+    match o {
+        Some(i) => format!("{}", i),
+        None => String::from("null"),
+    }
+}
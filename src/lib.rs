@@ -4,15 +4,24 @@ extern crate alloc;
 #[global_allocator]
 static ALLOC: dlmalloc::GlobalDlmalloc = dlmalloc::GlobalDlmalloc;
 
-mod silksong_memory;
+mod area;
+mod boss_practice;
+pub mod hits;
+pub mod load_removal;
+mod profile;
+mod room_timer;
+pub mod silksong_memory;
 pub mod splits;
+mod stats;
+mod steel_soul;
 mod timer;
 mod unstable;
 
-use alloc::{boxed::Box, format, vec::Vec};
+use alloc::{boxed::Box, format, string::String, vec::Vec};
 use asr::{
     future::{next_tick, retry},
     settings::Gui,
+    time_util::Instant,
     timer::TimerState,
     Address64, Process,
 };
@@ -24,14 +33,25 @@ use ugly_widget::{
 };
 
 use crate::{
+    area::AreaStore,
+    boss_practice::{BossFlags, BossRecord, PracticeBoss},
+    hits::{
+        DamageSnapshot, HitCause, HitCounts, HitLogSegment, HitMode, HitlessMode, HitlessSegment,
+    },
+    load_removal::{
+        format_drift, format_duration, GameTimeSource, LoadCause, LoadRemovalRules,
+        LoadRemovalSnapshot, LoadRemovalState, LoadSignal, LoadTimes,
+    },
+    profile::{ProfileChange, ProfileEvent, ProfileTracker},
+    room_timer::{self, RoomTimer},
     silksong_memory::{
-        attach_silksong, GameManagerPointers, Memory, PlayerDataPointers, SceneStore,
-        GAME_STATE_CUTSCENE, GAME_STATE_ENTERING_LEVEL, GAME_STATE_EXITING_LEVEL,
-        GAME_STATE_INACTIVE, GAME_STATE_LOADING, GAME_STATE_MAIN_MENU, GAME_STATE_PLAYING,
-        HERO_TRANSITION_STATE_WAITING_TO_ENTER_LEVEL, MENU_TITLE, QUIT_TO_MENU, UI_STATE_CUTSCENE,
-        UI_STATE_PAUSED, UI_STATE_PLAYING,
+        attach_silksong, gm_fields, is_menu, pd_fields, GameManagerPointers, Memory,
+        PlayerDataPointers, SceneStore, TickReads, TickSnapshot, GAME_STATE_PLAYING, MENU_TITLE,
     },
-    timer::SplitterAction,
+    splits::SplitPointer,
+    stats::{PlayerStat, StatVariables},
+    steel_soul::SteelSoulDeath,
+    timer::{SplitterAction, TimerMode},
 };
 
 asr::async_main!(stable);
@@ -48,23 +68,74 @@ struct AutoSplitterState {
     timer_state: TimerState,
     split_index: Option<u64>,
     segments_splitted: Vec<bool>,
-    look_for_teleporting: bool,
+    load_removal: LoadRemovalState,
     #[cfg(debug_assertions)]
     last_ui_state: i32,
-    last_game_state: i32,
     #[cfg(debug_assertions)]
     last_hero_transition_state: i32,
-    hits: i64,
-    segment_hits: Vec<i64>,
-    cumulative_hits: Vec<i64>,
-    comparison_hits: Vec<i64>,
+    hits: HitCounts,
+    segment_hits: Vec<HitCounts>,
+    cumulative_hits: Vec<HitCounts>,
+    comparison_hits: Vec<Option<i64>>,
+    comparison_hits_by_cause: Vec<Option<HitCounts>>,
+    best_segment_hits: Vec<Option<i64>>,
+    comparison_fingerprint: String,
     last_recoil: bool,
     last_hazard: bool,
     last_health_0: bool,
+    last_damage: Option<DamageSnapshot>,
+    masks_lost: i64,
+    hitless_failed: bool,
+    stat_variables: StatVariables,
+    area: AreaStore,
+    last_hero_death_count: Option<i32>,
+    deaths: i64,
+    segment_deaths: i64,
+    segment_deaths_index: Option<u64>,
+    death_scenes: Vec<String>,
+    /// The cocoon scene from before a death, and the scene of the death,
+    /// while waiting for the new cocoon
+    death_scene_pending: Option<(String, String)>,
+    last_corpse_scene: String,
+    last_permadeath_mode: i32,
+    steel_soul_dead: bool,
+    room_timer: RoomTimer,
+    /// The Set IL Start Room setting on the last tick, `None` before the first
+    last_il_set_start_room: Option<bool>,
+    /// The Set Boss Arena setting on the last tick, `None` before the first
+    last_boss_set_arena: Option<bool>,
+    /// Whether the auto splitter made a split since the last update,
+    /// so that a run ended by it isn't counted as ended in the timer
+    split_made: bool,
+    /// Whether the run was ended early by skipping to the last split,
+    /// so that the hits at the skipped splits aren't saved as comparisons
+    run_incomplete: bool,
+    boss_flags: BossFlags,
+    boss_record: Option<(PracticeBoss, BossRecord)>,
     #[cfg(debug_assertions)]
     last_health: Option<i32>,
     #[cfg(debug_assertions)]
     last_paused: bool,
+    last_load: Option<(LoadCause, Instant)>,
+    run_loads: LoadTimes,
+    segment_loads: LoadTimes,
+    segment_loads_index: Option<u64>,
+    last_load_disagreement: bool,
+    /// Whether the loading flags detected a load on the last tick
+    last_flags_loading: bool,
+    /// The game's own duration of the load in progress, in seconds
+    load_duration: f32,
+    profile: ProfileTracker,
+    game_version: Option<String>,
+    pointers_diagnosed: bool,
+    /// The pointers that the configured splits read
+    split_pointers: Vec<SplitPointer>,
+    /// The play time when the run started, for the in-game clock
+    play_time_start: Option<f32>,
+    /// Game time with loads removed, tracked to compare against the in-game clock
+    load_removed_time: asr::time::Duration,
+    last_game_time_tick: Option<Instant>,
+    last_drift: String,
 }
 
 impl AutoSplitterState {
@@ -74,33 +145,311 @@ impl AutoSplitterState {
         let mut segments_splitted = Vec::new();
         segments_splitted.resize(split_index.unwrap_or_default() as usize, false);
         let comparison_hits = Settings::get_comparison_hits().unwrap_or_default();
+        let comparison_hits_by_cause = Settings::get_comparison_hits_by_cause();
+        let best_segment_hits = Settings::get_best_segment_hits();
+        let comparison_fingerprint = Settings::get_comparison_fingerprint().unwrap_or_default();
         AutoSplitterState {
             timer_state,
             split_index,
             segments_splitted,
-            look_for_teleporting: false,
+            load_removal: LoadRemovalState::new(),
             #[cfg(debug_assertions)]
             last_ui_state: 0,
-            last_game_state: GAME_STATE_INACTIVE,
             #[cfg(debug_assertions)]
             last_hero_transition_state: 0,
-            hits: 0,
+            hits: HitCounts::default(),
             segment_hits: Vec::new(),
             cumulative_hits: Vec::new(),
             comparison_hits,
+            comparison_hits_by_cause,
+            best_segment_hits,
+            comparison_fingerprint,
             last_recoil: false,
             last_hazard: false,
             last_health_0: false,
+            last_damage: None,
+            masks_lost: 0,
+            hitless_failed: false,
+            stat_variables: StatVariables::default(),
+            area: AreaStore::default(),
+            last_hero_death_count: None,
+            deaths: 0,
+            segment_deaths: 0,
+            segment_deaths_index: split_index,
+            death_scenes: Vec::new(),
+            death_scene_pending: None,
+            last_corpse_scene: String::new(),
+            last_permadeath_mode: 0,
+            steel_soul_dead: false,
+            room_timer: RoomTimer::default(),
+            last_il_set_start_room: None,
+            last_boss_set_arena: None,
+            split_made: false,
+            run_incomplete: false,
+            boss_flags: BossFlags::default(),
+            boss_record: None,
             #[cfg(debug_assertions)]
             last_health: None,
             #[cfg(debug_assertions)]
             last_paused: false,
+            last_load: None,
+            run_loads: LoadTimes::default(),
+            segment_loads: LoadTimes::default(),
+            segment_loads_index: split_index,
+            last_load_disagreement: false,
+            last_flags_loading: false,
+            load_duration: 0.0,
+            profile: ProfileTracker::default(),
+            game_version: None,
+            pointers_diagnosed: false,
+            split_pointers: Vec::new(),
+            play_time_start: None,
+            load_removed_time: asr::time::Duration::ZERO,
+            last_game_time_tick: None,
+            last_drift: String::new(),
+        }
+    }
+
+    /// Clears everything tracked for the current run, after a reset
+    fn clear_run(&mut self, settings: &Settings) {
+        self.segments_splitted.clear();
+        self.split_made = false;
+        self.run_incomplete = false;
+        self.hits = HitCounts::default();
+        self.segment_hits.clear();
+        self.cumulative_hits.clear();
+        self.last_damage = None;
+        self.masks_lost = 0;
+        self.hitless_failed = false;
+        if settings.get_hitless() == HitlessMode::Off {
+            asr::timer::set_variable("hitless", DASH);
+        } else {
+            asr::timer::set_variable("hitless", "OK");
+        }
+        if settings.get_hit_counter() {
+            asr::timer::set_variable_int("hits", self.hits.total());
+            asr::timer::set_variable_int("segment hits", 0);
+            asr::timer::set_variable_int("masks lost", self.masks_lost);
+            self.set_hit_cause_variables();
+            self.set_best_segment_hits_variable(0);
+        } else {
+            asr::timer::set_variable("hits", DASH);
+            asr::timer::set_variable("segment hits", DASH);
+            asr::timer::set_variable("masks lost", DASH);
+            asr::timer::set_variable("enemy hits", DASH);
+            asr::timer::set_variable("hazard hits", DASH);
+            asr::timer::set_variable("death hits", DASH);
+            asr::timer::set_variable("best segment hits", DASH);
+        }
+        self.load_removal = LoadRemovalState::new();
+        #[cfg(debug_assertions)]
+        {
+            self.last_paused = false;
+        }
+        self.last_load = None;
+        self.run_loads = LoadTimes::default();
+        self.segment_loads = LoadTimes::default();
+        self.play_time_start = None;
+        self.load_removed_time = asr::time::Duration::ZERO;
+        self.last_game_time_tick = None;
+        self.last_drift.clear();
+        self.deaths = 0;
+        self.segment_deaths = 0;
+        self.death_scenes.clear();
+        self.death_scene_pending = None;
+        self.steel_soul_dead = false;
+        if settings.get_death_variables() {
+            asr::timer::set_variable_int("deaths", self.deaths);
+            asr::timer::set_variable_int("segment deaths", self.segment_deaths);
+        } else {
+            asr::timer::set_variable("deaths", DASH);
+            asr::timer::set_variable("segment deaths", DASH);
+        }
+        asr::timer::set_variable("death scene", DASH);
+        asr::timer::set_variable("loads removed", &self.run_loads.to_variable_string());
+        asr::timer::set_variable("segment loads", &self.segment_loads.to_variable_string());
+        asr::timer::set_variable("game time drift", DASH);
+        self.profile = ProfileTracker::default();
+        asr::timer::set_variable("save slot", DASH);
+    }
+
+    /// Writes a hit log record of this run to the log, `event` being reset or finish
+    fn log_hits(&self, settings: &Settings, event: &str) {
+        let names: Vec<String> = settings
+            .get_splits()
+            .iter()
+            .skip(1)
+            .map(splits::split_description)
+            .collect();
+        let segments: Vec<HitLogSegment> = self
+            .segment_hits
+            .iter()
+            .enumerate()
+            .map(|(i, hits)| HitLogSegment {
+                name: names.get(i).map_or("", String::as_str),
+                hits: *hits,
+                cumulative: self.cumulative_hits.get(i).map(HitCounts::total),
+                comparison: self.comparison_hits.get(i).copied().flatten(),
+            })
+            .collect();
+        let masks_lost = (settings.get_hit_mode() == HitMode::Damage).then_some(self.masks_lost);
+        let record = hits::hit_log_record(
+            event,
+            &self.comparison_fingerprint,
+            &segments,
+            masks_lost,
+            &self.death_scenes,
+        );
+        asr::print_message(&format!("{}{}", hits::HIT_LOG_PREFIX, record));
+    }
+
+    /// Saves the hits from this run into the comparisons, best segments, and run history,
+    /// unless the run was ended early
+    fn save_comparison_hits(&mut self, settings: &Settings) {
+        if self.run_incomplete {
+            return;
+        }
+        Settings::update_comparison_hits(
+            &mut self.comparison_hits,
+            &self
+                .cumulative_hits
+                .iter()
+                .map(HitCounts::total)
+                .collect::<Vec<i64>>(),
+        );
+        Settings::update_comparison_hits_by_cause(
+            &mut self.comparison_hits_by_cause,
+            &self.cumulative_hits,
+        );
+        let run_segment_hits = self.run_segment_hits();
+        if !run_segment_hits.is_empty() {
+            Settings::update_best_segment_hits(&mut self.best_segment_hits, &run_segment_hits);
+            Settings::push_hit_history(&run_segment_hits);
+            self.set_sum_of_best_hits_variable(settings);
+        }
+    }
+
+    /// The hits in each segment finished this run,
+    /// `None` for segments that were skipped or came after a skip
+    fn run_segment_hits(&self) -> Vec<Option<i64>> {
+        self.segments_splitted
+            .iter()
+            .enumerate()
+            .map(|(i, splitted)| {
+                let timed = *splitted && (i == 0 || self.segments_splitted[i - 1]);
+                let hits = self.segment_hits.get(i)?.total();
+                timed.then_some(hits)
+            })
+            .collect()
+    }
+
+    /// Remaps or discards the comparisons when the splits are different
+    /// from the splits they were recorded for
+    fn remap_comparisons(&mut self, settings: &mut Settings) {
+        let splits: Vec<String> = settings
+            .get_splits()
+            .iter()
+            .map(|s| options_str(s).to_string())
+            .collect();
+        let fingerprint = hits::splits_fingerprint(&splits);
+        if fingerprint == self.comparison_fingerprint {
+            return;
+        }
+        if let Some(old_splits) = Settings::get_comparison_splits() {
+            let map = hits::remap_splits(&old_splits, &splits);
+            self.comparison_hits = hits::remap_cumulative(&self.comparison_hits, &map);
+            self.comparison_hits_by_cause =
+                hits::remap_cumulative(&self.comparison_hits_by_cause, &map);
+            self.best_segment_hits = hits::remap_segments(&self.best_segment_hits, &map);
+            settings.remap_hitless_segments(&map);
+            asr::print_message(&format!(
+                "comparisons remapped: {} -> {}",
+                self.comparison_fingerprint, fingerprint
+            ));
+        } else {
+            // recorded before comparisons were keyed by splits,
+            // keep them only if they can still fit
+            let n = splits.len().saturating_sub(1);
+            if n < self.comparison_hits.len() || n < self.comparison_hits_by_cause.len() {
+                self.comparison_hits.clear();
+                self.comparison_hits_by_cause.clear();
+                self.best_segment_hits.clear();
+                asr::print_message("comparisons discarded: splits changed");
+            }
+        }
+        Settings::set_comparison_hits(&self.comparison_hits);
+        Settings::set_comparison_hits_by_cause(&self.comparison_hits_by_cause);
+        Settings::set_best_segment_hits(&self.best_segment_hits);
+        Settings::set_comparison_splits(&splits, &fingerprint);
+        self.comparison_fingerprint = fingerprint;
+        self.set_pb_hits_variable(settings);
+        if settings.get_hit_counter() {
+            self.set_sum_of_best_hits_variable(settings);
+        }
+    }
+
+    fn set_pb_hits_variable(&self, settings: &Settings) {
+        match self.comparison_hits.last() {
+            Some(Some(pb_hits)) if self.comparison_hits.len() + 1 == settings.get_splits_len() => {
+                asr::timer::set_variable_int("pb hits", *pb_hits);
+            }
+            _ => asr::timer::set_variable("pb hits", DASH),
         }
     }
 
+    fn set_best_segment_hits_variable(&self, i: usize) {
+        if let Some(Some(b)) = self.best_segment_hits.get(i) {
+            asr::timer::set_variable_int("best segment hits", *b);
+        } else {
+            asr::timer::set_variable("best segment hits", DASH);
+        }
+    }
+
+    fn set_sum_of_best_hits_variable(&self, settings: &Settings) {
+        if let Some(sob) =
+            hits::sum_of_best_hits(&self.best_segment_hits, settings.get_splits_len())
+        {
+            asr::timer::set_variable_int("sum of best hits", sob);
+        } else {
+            asr::timer::set_variable("sum of best hits", DASH);
+        }
+    }
+
+    fn set_hit_cause_variables(&self) {
+        asr::timer::set_variable_int("enemy hits", self.hits.enemy);
+        asr::timer::set_variable_int("hazard hits", self.hits.hazard);
+        asr::timer::set_variable_int("death hits", self.hits.deaths);
+    }
+
+    /// Updates the segment hits, comparison hits, and delta hits variables for segment `i`
+    fn set_segment_hit_variables(&self, i: usize) {
+        asr::timer::set_variable_int("segment hits", self.segment_hits[i].total());
+        if let Some(Some(c)) = self.comparison_hits.get(i) {
+            asr::timer::set_variable_int("comparison hits", *c);
+            asr::timer::set_variable_int("delta hits", self.hits.total() - c);
+        } else {
+            asr::timer::set_variable("comparison hits", DASH);
+            asr::timer::set_variable("delta hits", DASH);
+        }
+        self.set_best_segment_hits_variable(i);
+    }
+
+    /// Undoes splits back to split index `new_i`,
+    /// moving the hits from the undone segments into segment `new_i`
+    fn undo_to(&mut self, new_i: usize) {
+        hits::undo_to(
+            &mut self.segments_splitted,
+            &mut self.segment_hits,
+            &mut self.cumulative_hits,
+            new_i,
+        );
+    }
+
     fn update(&mut self, settings: &Settings) {
         let new_state = asr::timer::state();
         let new_index = unstable::timer_current_split_index();
+        // only a split since the last update can have ended the run
+        let split_made = core::mem::take(&mut self.split_made);
         if new_state == self.timer_state && new_index == self.split_index {
             return;
         }
@@ -112,13 +461,14 @@ impl AutoSplitterState {
                     || self.timer_state == TimerState::Ended =>
             {
                 // Reset
-                if settings.get_hit_counter() {
-                    Settings::update_comparison_hits(
-                        &mut self.comparison_hits,
-                        &self.cumulative_hits,
-                    );
+                if settings.get_hit_counter() && settings.get_timer_mode() != TimerMode::RoomTimer {
+                    // a finished run was already logged on the finish
+                    if self.timer_state != TimerState::Ended {
+                        self.log_hits(settings, "reset");
+                    }
+                    self.save_comparison_hits(settings);
                     if self.timer_state == TimerState::Ended {
-                        if let Some(pb_hits) = self.comparison_hits.last() {
+                        if let Some(Some(pb_hits)) = self.comparison_hits.last() {
                             asr::timer::set_variable_int("pb hits", *pb_hits);
                         }
                     }
@@ -127,28 +477,26 @@ impl AutoSplitterState {
                 {
                     self.split_index = None;
                 }
-                self.segments_splitted.clear();
-                self.hits = 0;
-                self.segment_hits.clear();
-                self.cumulative_hits.clear();
+                self.clear_run(settings);
+            }
+            TimerState::Running | TimerState::Paused if self.timer_state == TimerState::Ended => {
+                // Undo the final split
+                let new_i = hits::undo_end_split_index(self.split_index, new_index) as usize;
+                self.split_index = Some(new_i as u64);
+                self.undo_to(new_i);
+                // the End drained the comparisons, get them back
+                self.comparison_hits = Settings::get_comparison_hits().unwrap_or_default();
+                self.comparison_hits_by_cause = Settings::get_comparison_hits_by_cause();
                 if settings.get_hit_counter() {
-                    asr::timer::set_variable_int("hits", self.hits);
-                    asr::timer::set_variable_int("segment hits", 0);
-                } else {
-                    asr::timer::set_variable("hits", DASH);
-                    asr::timer::set_variable("segment hits", DASH);
-                }
-                self.look_for_teleporting = false;
-                self.last_game_state = GAME_STATE_INACTIVE;
-                #[cfg(debug_assertions)]
-                {
-                    self.last_paused = false;
+                    self.set_segment_hit_variables(new_i);
                 }
             }
             TimerState::Running if is_timer_state_between_runs(self.timer_state) => {
                 // Start
-                self.segment_hits
-                    .resize(new_index.unwrap_or_default() as usize + 1, 0);
+                self.segment_hits.resize(
+                    new_index.unwrap_or_default() as usize + 1,
+                    HitCounts::default(),
+                );
             }
             TimerState::Paused if self.timer_state == TimerState::Running => {
                 // Pause
@@ -161,49 +509,36 @@ impl AutoSplitterState {
                     || self.timer_state == TimerState::Paused =>
             {
                 // End
-                #[cfg(not(feature = "unstable"))]
-                {
-                    self.split_index = Some(self.split_index.unwrap_or_default() + 1);
-                }
-                #[cfg(feature = "unstable")]
-                match new_index {
-                    Some(new_idx) if self.split_index.unwrap_or_default() < new_idx => {
-                        self.split_index = Some(new_idx)
-                    }
-                    _ => {
-                        self.split_index = Some(self.split_index.unwrap_or_default() + 1);
-                    }
-                }
+                let end_index = hits::end_split_index(self.split_index, new_index, split_made);
+                self.split_index = Some(end_index);
+                // without the split index, splits and skips made in the timer aren't seen,
+                // so count the segments between as skipped
+                hits::end_segments(&mut self.segments_splitted, end_index as usize);
                 if settings.get_hit_counter() {
                     if let Some(index) = self.split_index {
                         let i = index as usize;
                         self.cumulative_hits.resize(i, self.hits);
+                        self.log_hits(settings, "finish");
                         let cmp_len = self.comparison_hits.len();
                         if i < cmp_len {
                             self.comparison_hits.drain(0..(cmp_len - i));
                         }
+                        let cmp_len = self.comparison_hits_by_cause.len();
+                        if i < cmp_len {
+                            self.comparison_hits_by_cause.drain(0..(cmp_len - i));
+                        }
                     }
                 }
             }
             _ => {
+                // without the split index, undos and skips made in the timer mid-run aren't seen,
+                // so their hits stay in the segment they were counted in
                 #[cfg(feature = "unstable")]
                 if let (Some(new_index), Some(old_index)) = (&new_index, &self.split_index) {
                     let new_i = *new_index as usize;
                     if new_index < old_index {
                         // Undo
-                        self.segment_hits[new_i] +=
-                            self.segment_hits.drain((new_i + 1)..).sum::<i64>();
-                        if new_i < self.cumulative_hits.len() {
-                            let mut i = new_i;
-                            // go back through skipped splits
-                            while 1 <= i && !self.segments_splitted[i - 1] {
-                                i -= 1;
-                            }
-                            // segment [i - 1] was not skipped, but segment [i] was skipped or undone,
-                            // so remove cumulative_hits from there on
-                            self.cumulative_hits.truncate(i);
-                        }
-                        self.segments_splitted.truncate(new_i);
+                        self.undo_to(new_i);
                     } else if new_index > old_index {
                         for old_idx in (*old_index)..(*new_index) {
                             let o_i = old_idx as usize;
@@ -213,24 +548,17 @@ impl AutoSplitterState {
                             self.segments_splitted.push(splitted);
                             if splitted {
                                 // Split
-                                self.segment_hits.push(0);
+                                self.segment_hits.push(HitCounts::default());
                                 self.cumulative_hits.resize(n_i, self.hits);
                             } else {
                                 // Skip
-                                self.segment_hits.insert(o_i, 0);
+                                self.segment_hits.insert(o_i, HitCounts::default());
                             }
                         }
                     }
 
                     if settings.get_hit_counter() && new_index != old_index {
-                        asr::timer::set_variable_int("segment hits", self.segment_hits[new_i]);
-                        if let Some(c) = self.comparison_hits.get(new_i) {
-                            asr::timer::set_variable_int("comparison hits", *c);
-                            asr::timer::set_variable_int("delta hits", self.hits - c);
-                        } else {
-                            asr::timer::set_variable("comparison hits", DASH);
-                            asr::timer::set_variable("delta hits", DASH);
-                        }
+                        self.set_segment_hit_variables(new_i);
                     }
                 }
             }
@@ -250,26 +578,359 @@ const TICKS_PER_GUI: usize = 0x100;
 
 #[derive(Gui)]
 struct Settings {
+    /// Timer Mode
+    timer_mode: TimerMode,
+    /// Set IL Start Room
+    ///
+    /// In IL Practice, turn this on while in a room to make it the start room,
+    /// entered from the room before it. Going back from the start room to that room resets
+    il_set_start_room: bool,
+    /// Practice Boss
+    ///
+    /// In Boss Practice, the boss to start on encountering,
+    /// or on entering its arena once the arena is known
+    practice_boss: PracticeBoss,
+    /// Set Boss Arena
+    ///
+    /// In Boss Practice, turn this on while in the practice boss's arena to make it the arena,
+    /// for a boss encountered before the arena was learned
+    boss_set_arena: bool,
+    /// Load Removal
+    load_removal: LoadRemovalRules,
+    /// Load Detection
+    load_signal: LoadSignal,
+    /// Game Time Source
+    game_time_source: GameTimeSource,
     /// Hit Counter
+    ///
+    /// Counts hits for each segment.
+    /// The stable build only sees undoing the final split, not undos and skips made mid-run
     #[default = true]
     hit_counter: bool,
+    /// Hit Counting
+    hit_mode: HitMode,
+    /// Hitless
+    hitless: HitlessMode,
+    /// Steel Soul Death
+    ///
+    /// What to do when a Steel Soul run dies
+    steel_soul_death: SteelSoulDeath,
+    /// Save Slot Change
+    ///
+    /// What to do when a different save slot or a fresh file is loaded during a run
+    profile_change: ProfileChange,
+    /// Health Variables
+    ///
+    /// Shows health and max health as variables
+    stat_health: bool,
+    /// Silk Variables
+    ///
+    /// Shows silk and max silk as variables
+    stat_silk: bool,
+    /// Rosaries Variable
+    ///
+    /// Shows rosaries as a variable
+    stat_rosaries: bool,
+    /// Shell Shards Variable
+    ///
+    /// Shows shell shards as a variable
+    stat_shell_shards: bool,
+    /// Completion Variable
+    ///
+    /// Shows the completion percentage as a variable
+    stat_completion: bool,
+    /// Area Variable
+    ///
+    /// Shows the current area as a variable
+    area_variable: bool,
+    /// Death Variables
+    ///
+    /// Shows deaths, segment deaths, and the scene of the last death as variables
+    death_variables: bool,
+    /// Game Time Drift Variable
+    ///
+    /// Shows how far load removal drifts from the in-game clock as a variable
+    game_time_drift: bool,
     /// Splits
     #[heading_level = 1]
     splits: UglyList<splits::Split>,
+    /// Hitless Segments
+    ///
+    /// One entry for each segment, in the same order as the splits.
+    /// Segments without an entry are hitless
+    #[heading_level = 1]
+    hitless_segments: UglyList<HitlessSegment>,
 }
 
 impl StoreGui for Settings {
     fn insert_into(&self, settings_map: &asr::settings::Map) -> bool {
+        let m = self.timer_mode.insert_into(settings_map, "timer_mode");
+        let i = self
+            .il_set_start_room
+            .insert_into(settings_map, "il_set_start_room");
+        let pb = self
+            .practice_boss
+            .insert_into(settings_map, "practice_boss");
+        let ba = self
+            .boss_set_arena
+            .insert_into(settings_map, "boss_set_arena");
+        let l = self.load_removal.insert_into(settings_map, "load_removal");
+        let s = self.load_signal.insert_into(settings_map, "load_signal");
+        let g = self
+            .game_time_source
+            .insert_into(settings_map, "game_time_source");
         let a = self.hit_counter.insert_into(settings_map, "hit_counter");
+        let h = self.hit_mode.insert_into(settings_map, "hit_mode");
+        let f = self.hitless.insert_into(settings_map, "hitless");
+        let d = self
+            .steel_soul_death
+            .insert_into(settings_map, "steel_soul_death");
+        let pc = self
+            .profile_change
+            .insert_into(settings_map, "profile_change");
+        let sh = self.stat_health.insert_into(settings_map, "stat_health");
+        let ss = self.stat_silk.insert_into(settings_map, "stat_silk");
+        let sr = self
+            .stat_rosaries
+            .insert_into(settings_map, "stat_rosaries");
+        let sk = self
+            .stat_shell_shards
+            .insert_into(settings_map, "stat_shell_shards");
+        let sc = self
+            .stat_completion
+            .insert_into(settings_map, "stat_completion");
+        let av = self
+            .area_variable
+            .insert_into(settings_map, "area_variable");
+        let dv = self
+            .death_variables
+            .insert_into(settings_map, "death_variables");
+        let gd = self
+            .game_time_drift
+            .insert_into(settings_map, "game_time_drift");
         let b = self.splits.insert_into(settings_map, "splits");
-        a || b
+        let e = self
+            .hitless_segments
+            .insert_into(settings_map, "hitless_segments");
+        m || i
+            || pb
+            || ba
+            || l
+            || s
+            || g
+            || a
+            || h
+            || f
+            || d
+            || pc
+            || sh
+            || ss
+            || sr
+            || sk
+            || sc
+            || av
+            || dv
+            || gd
+            || b
+            || e
     }
 }
 
 impl Settings {
+    pub fn get_timer_mode(&self) -> TimerMode {
+        self.timer_mode.clone()
+    }
+    pub fn get_il_set_start_room(&self) -> bool {
+        self.il_set_start_room
+    }
+    pub fn get_practice_boss(&self) -> PracticeBoss {
+        self.practice_boss.clone()
+    }
+    pub fn get_boss_set_arena(&self) -> bool {
+        self.boss_set_arena
+    }
+    pub fn get_load_removal(&self) -> LoadRemovalRules {
+        self.load_removal.clone()
+    }
+    pub fn get_load_signal(&self) -> LoadSignal {
+        self.load_signal.clone()
+    }
+    pub fn get_game_time_source(&self) -> GameTimeSource {
+        self.game_time_source.clone()
+    }
     pub fn get_hit_counter(&self) -> bool {
         self.hit_counter
     }
+    pub fn get_hit_mode(&self) -> HitMode {
+        self.hit_mode.clone()
+    }
+    pub fn get_hitless(&self) -> HitlessMode {
+        self.hitless.clone()
+    }
+    pub fn get_stat(&self, stat: PlayerStat) -> bool {
+        match stat {
+            PlayerStat::Health | PlayerStat::MaxHealth => self.stat_health,
+            PlayerStat::Silk | PlayerStat::MaxSilk => self.stat_silk,
+            PlayerStat::Rosaries => self.stat_rosaries,
+            PlayerStat::ShellShards => self.stat_shell_shards,
+        }
+    }
+    pub fn get_steel_soul_death(&self) -> SteelSoulDeath {
+        self.steel_soul_death.clone()
+    }
+    pub fn get_profile_change(&self) -> ProfileChange {
+        self.profile_change.clone()
+    }
+    pub fn get_stat_completion(&self) -> bool {
+        self.stat_completion
+    }
+    pub fn get_area_variable(&self) -> bool {
+        self.area_variable
+    }
+    pub fn get_death_variables(&self) -> bool {
+        self.death_variables
+    }
+    pub fn get_game_time_drift(&self) -> bool {
+        self.game_time_drift
+    }
+    /// Whether the deaths are read, for the death variables or for Steel Soul
+    pub fn get_deaths(&self) -> bool {
+        self.death_variables || self.steel_soul_death != SteelSoulDeath::Ignore
+    }
+    /// The pointers that the configured splits read, without duplicates
+    pub fn get_split_pointers(&self) -> Vec<SplitPointer> {
+        let mut pointers: Vec<SplitPointer> = self
+            .splits
+            .get_list()
+            .into_iter()
+            .flat_map(|s| splits::split_pointers(s).iter().copied())
+            .collect();
+        pointers.sort_unstable();
+        pointers.dedup();
+        pointers
+    }
+    /// The pointers that the configured splits and the enabled features read,
+    /// without duplicates
+    pub fn get_needed_pointers(&self) -> Vec<SplitPointer> {
+        use SplitPointer::{Gm, Pd};
+        let mut pointers = self.get_split_pointers();
+        pointers.extend([
+            Gm(gm_fields::accepting_input),
+            Gm(gm_fields::game_state),
+            Gm(gm_fields::hazard_respawning),
+            Gm(gm_fields::hero_transition_state),
+            Gm(gm_fields::scene_load_activation_allowed),
+            Gm(gm_fields::next_scene_name),
+            Gm(gm_fields::scene_name),
+            Gm(gm_fields::ui_state_vanilla),
+            Pd(pd_fields::health),
+            Pd(pd_fields::version),
+            Pd(pd_fields::revision_break),
+        ]);
+        if self.get_load_signal() != LoadSignal::Heuristic {
+            pointers.extend([
+                Gm(gm_fields::is_loading),
+                Gm(gm_fields::current_load_duration),
+                Gm(gm_fields::is_in_scene_transition),
+                Gm(gm_fields::is_waiting_for_scene_ready),
+            ]);
+        }
+        if self.get_hit_counter() {
+            pointers.extend([
+                Gm(gm_fields::hazard_death),
+                Gm(gm_fields::hero_recoil_frozen),
+            ]);
+            if self.get_hit_mode() == HitMode::Damage {
+                pointers.extend([
+                    Pd(pd_fields::health_blue),
+                    Pd(pd_fields::damaged_blue),
+                    Pd(pd_fields::damaged_purple),
+                ]);
+            }
+        }
+        for stat in PlayerStat::ALL {
+            if self.get_stat(stat) {
+                pointers.push(Pd(stat.field()));
+            }
+        }
+        if self.get_stat_completion() {
+            pointers.push(Pd(pd_fields::completion_percentage));
+        }
+        if self.get_area_variable() {
+            pointers.extend([Gm(gm_fields::map_zone_string), Pd(pd_fields::current_area)]);
+        }
+        let profile = self.get_profile_change() != ProfileChange::Ignore;
+        if self.get_game_time_source() == GameTimeSource::PlayTime
+            || self.get_game_time_drift()
+            || profile
+        {
+            pointers.push(Pd(pd_fields::play_time));
+        }
+        if profile {
+            pointers.extend([Gm(gm_fields::profile_id), Pd(pd_fields::profile_id)]);
+        }
+        if self.get_deaths() {
+            pointers.push(Gm(gm_fields::hero_death_count));
+        }
+        if self.get_death_variables() {
+            pointers.push(Pd(pd_fields::hero_corpse_scene));
+        }
+        if self.get_steel_soul_death() != SteelSoulDeath::Ignore {
+            pointers.push(Pd(pd_fields::permadeath_mode));
+        }
+        if self.get_timer_mode() == TimerMode::BossPractice {
+            pointers.extend(self.get_practice_boss().flag_fields().map(Pd));
+        }
+        pointers.sort_unstable();
+        pointers.dedup();
+        pointers
+    }
+    /// The hitless setting for the segment ending at split `i + 1`
+    pub fn get_hitless_segment(&self, i: u64) -> HitlessSegment {
+        self.hitless_segments
+            .get_list()
+            .get(i as usize)
+            .cloned()
+            .cloned()
+            .unwrap_or_default()
+    }
+    /// Moves the hitless segment settings along with the splits that end the segments
+    fn remap_hitless_segments(&mut self, map: &[Option<usize>]) {
+        let old_segments = self.hitless_segments.get_list();
+        if old_segments.is_empty() {
+            return;
+        }
+        let mut segments: Vec<HitlessSegment> = map
+            .iter()
+            .skip(1)
+            .map(|k| {
+                k.filter(|k| 1 <= *k)
+                    .and_then(|k| old_segments.get(k - 1).cloned().cloned())
+                    .unwrap_or_default()
+            })
+            .collect();
+        // segments without an entry are hitless
+        while segments.last() == Some(&HitlessSegment::Hitless) {
+            segments.pop();
+        }
+        loop {
+            let old = asr::settings::Map::load();
+            let new = old.clone();
+            let l = asr::settings::List::new();
+            for (i, segment) in segments.iter().enumerate() {
+                l.push(options_str(segment));
+                new.insert(
+                    &format!("hitless_segments_{}_item", i),
+                    options_str(segment),
+                );
+            }
+            new.insert("hitless_segments", l);
+            if new.store_if_unchanged(&old) {
+                break;
+            }
+        }
+        self.load_update_store_if_unchanged();
+    }
     pub fn get_splits_len(&self) -> usize {
         self.splits.get_list().len()
     }
@@ -287,32 +948,294 @@ impl Settings {
         gui
     }
 
-    pub fn get_comparison_hits() -> Option<Vec<i64>> {
+    pub fn get_comparison_hits() -> Option<Vec<Option<i64>>> {
         let c = asr::settings::Map::load().get("comparison_hits")?;
-        Some(c.get_list()?.iter().filter_map(|i| i.get_i64()).collect())
+        Some(segment_hits_from_list(&c.get_list()?))
     }
 
-    pub fn update_comparison_hits(comparison_hits: &mut Vec<i64>, cumulative_hits: &[i64]) {
+    pub fn update_comparison_hits(comparison_hits: &mut Vec<Option<i64>>, cumulative_hits: &[i64]) {
         // save cumulative_hits to comparison_hits
         for i in 0..cumulative_hits.len() {
             if i < comparison_hits.len() {
-                comparison_hits[i] = cmp::min(comparison_hits[i], cumulative_hits[i]);
+                comparison_hits[i] = Some(match comparison_hits[i] {
+                    Some(c) => cmp::min(c, cumulative_hits[i]),
+                    None => cumulative_hits[i],
+                });
             } else {
-                comparison_hits.push(cumulative_hits[i]);
+                comparison_hits.push(Some(cumulative_hits[i]));
             }
         }
         Settings::set_comparison_hits(comparison_hits);
     }
 
-    fn set_comparison_hits(comparison_hits: &[i64]) {
+    fn set_comparison_hits(comparison_hits: &[Option<i64>]) {
+        let l = segment_hits_to_list(comparison_hits);
+        loop {
+            let old = asr::settings::Map::load();
+            let new = old.clone();
+            new.insert("comparison_hits", &l);
+            if new.store_if_unchanged(&old) {
+                return;
+            }
+        }
+    }
+
+    pub fn get_comparison_hits_by_cause() -> Vec<Option<HitCounts>> {
+        let m = asr::settings::Map::load();
+        let get = |key: &str| -> Vec<Option<i64>> {
+            m.get(key)
+                .and_then(|c| Some(segment_hits_from_list(&c.get_list()?)))
+                .unwrap_or_default()
+        };
+        let enemy = get("comparison_enemy_hits");
+        let hazard = get("comparison_hazard_hits");
+        let deaths = get("comparison_deaths");
+        enemy
+            .into_iter()
+            .zip(hazard)
+            .zip(deaths)
+            .map(|((enemy, hazard), deaths)| {
+                Some(HitCounts {
+                    enemy: enemy?,
+                    hazard: hazard?,
+                    deaths: deaths?,
+                })
+            })
+            .collect()
+    }
+
+    pub fn update_comparison_hits_by_cause(
+        comparison_hits: &mut Vec<Option<HitCounts>>,
+        cumulative_hits: &[HitCounts],
+    ) {
+        // save the minimum of each cause separately
+        for (i, c) in cumulative_hits.iter().enumerate() {
+            if i < comparison_hits.len() {
+                comparison_hits[i] = Some(match comparison_hits[i] {
+                    Some(h) => h.min(*c),
+                    None => *c,
+                });
+            } else {
+                comparison_hits.push(Some(*c));
+            }
+        }
+        Settings::set_comparison_hits_by_cause(comparison_hits);
+    }
+
+    fn set_comparison_hits_by_cause(comparison_hits: &[Option<HitCounts>]) {
+        let enemy = asr::settings::List::new();
+        let hazard = asr::settings::List::new();
+        let deaths = asr::settings::List::new();
+        for c in comparison_hits {
+            enemy.push(c.map_or(NO_SEGMENT_HITS, |c| c.enemy));
+            hazard.push(c.map_or(NO_SEGMENT_HITS, |c| c.hazard));
+            deaths.push(c.map_or(NO_SEGMENT_HITS, |c| c.deaths));
+        }
+        loop {
+            let old = asr::settings::Map::load();
+            let new = old.clone();
+            new.insert("comparison_enemy_hits", &enemy);
+            new.insert("comparison_hazard_hits", &hazard);
+            new.insert("comparison_deaths", &deaths);
+            if new.store_if_unchanged(&old) {
+                return;
+            }
+        }
+    }
+
+    pub fn get_best_segment_hits() -> Vec<Option<i64>> {
+        asr::settings::Map::load()
+            .get("best_segment_hits")
+            .and_then(|c| Some(segment_hits_from_list(&c.get_list()?)))
+            .unwrap_or_default()
+    }
+
+    pub fn update_best_segment_hits(
+        best_segment_hits: &mut Vec<Option<i64>>,
+        run_segment_hits: &[Option<i64>],
+    ) {
+        hits::update_best_segment_hits(best_segment_hits, run_segment_hits);
+        Settings::set_best_segment_hits(best_segment_hits);
+    }
+
+    fn set_best_segment_hits(best_segment_hits: &[Option<i64>]) {
+        let l = segment_hits_to_list(best_segment_hits);
+        loop {
+            let old = asr::settings::Map::load();
+            let new = old.clone();
+            new.insert("best_segment_hits", &l);
+            if new.store_if_unchanged(&old) {
+                return;
+            }
+        }
+    }
+
+    /// The IL start room, and the room it was entered from
+    pub fn get_il_rooms() -> Option<(String, String)> {
+        let m = asr::settings::Map::load();
+        let start = m.get("il_start_room")?.get_string()?;
+        let entry = m.get("il_entry_room")?.get_string()?;
+        Some((start, entry))
+    }
+
+    fn set_il_rooms(start: &str, entry: &str) {
+        loop {
+            let old = asr::settings::Map::load();
+            let new = old.clone();
+            new.insert("il_start_room", start);
+            new.insert("il_entry_room", entry);
+            if new.store_if_unchanged(&old) {
+                return;
+            }
+        }
+    }
+
+    pub fn get_boss_record(boss: &PracticeBoss) -> BossRecord {
+        let Some(r) = asr::settings::Map::load()
+            .get("boss_practice")
+            .and_then(|b| b.get_map())
+            .and_then(|b| b.get(boss.key()))
+            .and_then(|r| r.get_map())
+        else {
+            return BossRecord::default();
+        };
+        BossRecord {
+            arena: r.get("arena").and_then(|a| a.get_string()),
+            attempts: r
+                .get("attempts")
+                .and_then(|a| a.get_i64())
+                .unwrap_or_default(),
+            kills: r.get("kills").and_then(|k| k.get_i64()).unwrap_or_default(),
+        }
+    }
+
+    fn set_boss_record(boss: &PracticeBoss, record: &BossRecord) {
+        let r = asr::settings::Map::new();
+        if let Some(arena) = &record.arena {
+            r.insert("arena", arena.as_str());
+        }
+        r.insert("attempts", record.attempts);
+        r.insert("kills", record.kills);
+        loop {
+            let old = asr::settings::Map::load();
+            let new = old.clone();
+            let boss_practice = old
+                .get("boss_practice")
+                .and_then(|b| b.get_map())
+                .unwrap_or_else(asr::settings::Map::new);
+            boss_practice.insert(boss.key(), &r);
+            new.insert("boss_practice", &boss_practice);
+            if new.store_if_unchanged(&old) {
+                return;
+            }
+        }
+    }
+
+    /// The best game time for a room, in milliseconds
+    pub fn get_room_best(key: &str) -> Option<i64> {
+        asr::settings::Map::load()
+            .get("room_game_time_bests")?
+            .get_map()?
+            .get(key)?
+            .get_i64()
+    }
+
+    /// Sets the best game time for a room,
+    /// dropping the least recently set rooms past `MAX_ROOM_BESTS`
+    fn set_room_best(key: &str, ms: i64) {
+        loop {
+            let old = asr::settings::Map::load();
+            let new = old.clone();
+            let old_bests = old
+                .get("room_game_time_bests")
+                .and_then(|r| r.get_map())
+                .unwrap_or_else(asr::settings::Map::new);
+            let old_keys: Vec<String> = old
+                .get("room_game_time_best_keys")
+                .and_then(|k| k.get_list())
+                .map(|l| l.iter().filter_map(|k| k.get_string()).collect())
+                .unwrap_or_default();
+            let room_bests = asr::settings::Map::new();
+            let keys = asr::settings::List::new();
+            for k in room_timer::room_best_keys(&old_keys, key) {
+                let best = if k == key {
+                    Some(ms)
+                } else {
+                    old_bests.get(&k).and_then(|b| b.get_i64())
+                };
+                if let Some(best) = best {
+                    room_bests.insert(&k, best);
+                    keys.push(k.as_str());
+                }
+            }
+            new.insert("room_game_time_bests", &room_bests);
+            new.insert("room_game_time_best_keys", &keys);
+            if new.store_if_unchanged(&old) {
+                return;
+            }
+        }
+    }
+
+    /// Updates the room best for `key` if `ms` is faster, returning the room best
+    pub fn update_room_best(key: &str, ms: i64) -> i64 {
+        match Settings::get_room_best(key) {
+            Some(best) if best <= ms => best,
+            _ => {
+                Settings::set_room_best(key, ms);
+                ms
+            }
+        }
+    }
+
+    /// The splits that the comparisons were recorded for
+    pub fn get_comparison_splits() -> Option<Vec<String>> {
+        let c = asr::settings::Map::load().get("comparison_splits")?;
+        Some(
+            c.get_list()?
+                .iter()
+                .filter_map(|s| s.get_string())
+                .collect(),
+        )
+    }
+
+    pub fn get_comparison_fingerprint() -> Option<String> {
+        asr::settings::Map::load()
+            .get("comparison_fingerprint")?
+            .get_string()
+    }
+
+    fn set_comparison_splits(splits: &[String], fingerprint: &str) {
         let l = asr::settings::List::new();
-        for i in comparison_hits {
-            l.push(*i);
+        for s in splits {
+            l.push(s.as_str());
         }
         loop {
             let old = asr::settings::Map::load();
             let new = old.clone();
-            new.insert("comparison_hits", &l);
+            new.insert("comparison_splits", &l);
+            new.insert("comparison_fingerprint", fingerprint);
+            if new.store_if_unchanged(&old) {
+                return;
+            }
+        }
+    }
+
+    /// Appends the segment hits of a run to the run history,
+    /// dropping the oldest runs past `HIT_HISTORY_LEN`
+    pub fn push_hit_history(run_segment_hits: &[Option<i64>]) {
+        let run = segment_hits_to_list(run_segment_hits);
+        loop {
+            let old = asr::settings::Map::load();
+            let new = old.clone();
+            let history = asr::settings::List::new();
+            if let Some(old_history) = old.get("hit_history").and_then(|h| h.get_list()) {
+                let dropped = (old_history.len() as usize).saturating_sub(HIT_HISTORY_LEN - 1);
+                for r in old_history.iter().skip(dropped) {
+                    history.push(r);
+                }
+            }
+            history.push(&run);
+            new.insert("hit_history", &history);
             if new.store_if_unchanged(&old) {
                 return;
             }
@@ -320,6 +1243,26 @@ impl Settings {
     }
 }
 
+/// The number of runs kept in the hit history
+const HIT_HISTORY_LEN: usize = 100;
+
+/// Segments and comparisons without hits are stored as `NO_SEGMENT_HITS`
+const NO_SEGMENT_HITS: i64 = -1;
+
+fn segment_hits_to_list(segment_hits: &[Option<i64>]) -> asr::settings::List {
+    let l = asr::settings::List::new();
+    for h in segment_hits {
+        l.push(h.unwrap_or(NO_SEGMENT_HITS));
+    }
+    l
+}
+
+fn segment_hits_from_list(l: &asr::settings::List) -> Vec<Option<i64>> {
+    l.iter()
+        .map(|h| h.get_i64().filter(|h| *h != NO_SEGMENT_HITS))
+        .collect()
+}
+
 fn default_splits_init() -> asr::settings::Map {
     let settings1 = asr::settings::Map::load();
     if settings1
@@ -374,30 +1317,73 @@ async fn main() {
     // register the variables on start
     asr::timer::set_variable("hits", DASH);
     asr::timer::set_variable("segment hits", DASH);
+    asr::timer::set_variable("masks lost", DASH);
     asr::timer::set_variable("pb hits", DASH);
     asr::timer::set_variable("comparison hits", DASH);
     asr::timer::set_variable("delta hits", DASH);
+    asr::timer::set_variable("enemy hits", DASH);
+    asr::timer::set_variable("hazard hits", DASH);
+    asr::timer::set_variable("death hits", DASH);
+    asr::timer::set_variable("deaths", DASH);
+    asr::timer::set_variable("segment deaths", DASH);
+    asr::timer::set_variable("death scene", DASH);
+    asr::timer::set_variable("best segment hits", DASH);
+    asr::timer::set_variable("sum of best hits", DASH);
+    asr::timer::set_variable("loads removed", DASH);
+    asr::timer::set_variable("segment loads", DASH);
+    asr::timer::set_variable("last load", DASH);
+    asr::timer::set_variable("game time drift", DASH);
+    asr::timer::set_variable("save slot", DASH);
+    asr::timer::set_variable("game version", DASH);
+    asr::timer::set_variable("diagnostics", DASH);
+    asr::timer::set_variable("hitless", DASH);
+    asr::timer::set_variable("area", DASH);
+    asr::timer::set_variable("last room time", DASH);
+    asr::timer::set_variable("room best", DASH);
+    asr::timer::set_variable("current room", DASH);
+    asr::timer::set_variable("il start room", DASH);
+    asr::timer::set_variable("boss attempts", DASH);
+    asr::timer::set_variable("boss kills", DASH);
 
     asr::print_message("Hello, World!");
 
     let mut ticks_since_gui = 0;
     let mut settings = Settings::default_init_register();
+    asr::print_message(&format!("timer_mode: {:?}", settings.get_timer_mode()));
+    asr::print_message(&format!("load_removal: {:?}", settings.get_load_removal()));
+    asr::print_message(&format!("load_signal: {:?}", settings.get_load_signal()));
     asr::print_message(&format!("hit_counter: {:?}", settings.get_hit_counter()));
+    asr::print_message(&format!("hit_mode: {:?}", settings.get_hit_mode()));
+    asr::print_message(&format!("hitless: {:?}", settings.get_hitless()));
+    asr::print_message(&format!(
+        "steel_soul_death: {:?}",
+        settings.get_steel_soul_death()
+    ));
     asr::print_message(&format!("splits: {:?}", settings.get_splits()));
 
     let mut state = AutoSplitterState::new();
+    state.remap_comparisons(&mut settings);
+    state.split_pointers = settings.get_split_pointers();
 
     if settings.get_hit_counter() {
         asr::timer::set_variable_int("hits", 0);
         asr::timer::set_variable_int("segment hits", 0);
+        asr::timer::set_variable_int("masks lost", 0);
+        state.set_hit_cause_variables();
+        state.set_best_segment_hits_variable(0);
+        state.set_sum_of_best_hits_variable(&settings);
     }
 
-    if !state.comparison_hits.is_empty()
-        && (state.comparison_hits.len() + 1 == settings.get_splits_len())
-    {
-        if let Some(pb_hits) = state.comparison_hits.last() {
-            asr::timer::set_variable_int("pb hits", *pb_hits);
-        }
+    state.set_pb_hits_variable(&settings);
+    if let Some((start, _)) = Settings::get_il_rooms() {
+        asr::timer::set_variable("il start room", &start);
+    }
+    if settings.get_death_variables() {
+        asr::timer::set_variable_int("deaths", 0);
+        asr::timer::set_variable_int("segment deaths", 0);
+    }
+    if settings.get_hitless() != HitlessMode::Off {
+        asr::timer::set_variable("hitless", "OK");
     }
 
     loop {
@@ -411,14 +1397,13 @@ async fn main() {
                 next_tick().await;
                 let mem = Memory::wait_attach(&process).await;
                 next_tick().await;
-                let gm = Box::new(GameManagerPointers::new());
-                let pd = Box::new(PlayerDataPointers::new());
+                let mut gm = Box::new(GameManagerPointers::new());
+                let mut pd = Box::new(PlayerDataPointers::new());
+                state.game_version = None;
+                state.pointers_diagnosed = false;
                 let _: bool = mem.deref(&gm.accepting_input).unwrap_or_default();
-                let _: Address64 = mem.deref(&gm.entry_gate_name).unwrap_or_default();
                 let _: i32 = mem.deref(&gm.game_state).unwrap_or_default();
-                let _: bool = mem.deref(&gm.hazard_death).unwrap_or_default();
                 let _: bool = mem.deref(&gm.hazard_respawning).unwrap_or_default();
-                let _: bool = mem.deref(&gm.hero_recoil_frozen).unwrap_or_default();
                 let _: i32 = mem.deref(&gm.hero_transition_state).unwrap_or_default();
                 let _: bool = mem
                     .deref(&gm.scene_load_activation_allowed)
@@ -426,7 +1411,71 @@ async fn main() {
                 let _: Address64 = mem.deref(&gm.next_scene_name).unwrap_or_default();
                 let _: Address64 = mem.deref(&gm.scene_name).unwrap_or_default();
                 let _: i32 = mem.deref(&gm.ui_state_vanilla).unwrap_or_default();
+                if settings.get_load_signal() != LoadSignal::Heuristic {
+                    let _: bool = mem.deref(&gm.is_loading).unwrap_or_default();
+                    let _: f32 = mem.deref(&gm.current_load_duration).unwrap_or_default();
+                    let _: bool = mem.deref(&gm.is_in_scene_transition).unwrap_or_default();
+                    let _: bool = mem
+                        .deref(&gm.is_waiting_for_scene_ready)
+                        .unwrap_or_default();
+                }
                 let _: i32 = mem.deref(&pd.health).unwrap_or_default();
+                if settings.get_hit_counter() {
+                    let _: bool = mem.deref(&gm.hazard_death).unwrap_or_default();
+                    let _: bool = mem.deref(&gm.hero_recoil_frozen).unwrap_or_default();
+                    if settings.get_hit_mode() == HitMode::Damage {
+                        let _: i32 = mem.deref(&pd.health_blue).unwrap_or_default();
+                        let _: bool = mem.deref(&pd.damaged_blue).unwrap_or_default();
+                        let _: bool = mem.deref(&pd.damaged_purple).unwrap_or_default();
+                    }
+                }
+                for stat in PlayerStat::ALL {
+                    if settings.get_stat(stat) {
+                        let _ = stat.read(&mem, &pd);
+                    }
+                }
+                if settings.get_stat_completion() {
+                    let _: f32 = mem.deref(&pd.completion_percentage).unwrap_or_default();
+                }
+                if settings.get_area_variable() {
+                    let _ = area::area_name(&mem, &gm, &pd);
+                    let _: i32 = mem.deref(&pd.map_zone).unwrap_or_default();
+                }
+                let profile = settings.get_profile_change() != ProfileChange::Ignore;
+                if settings.get_game_time_source() == GameTimeSource::PlayTime
+                    || settings.get_game_time_drift()
+                    || profile
+                {
+                    let _: f32 = mem.deref(&pd.play_time).unwrap_or_default();
+                }
+                if profile {
+                    let _: i32 = mem.deref(&gm.profile_id).unwrap_or_default();
+                    let _: i32 = mem.deref(&pd.profile_id).unwrap_or_default();
+                }
+                // the version picks the pointer paths, the revision break is read with it
+                let _ = mem.read_string(&pd.version);
+                if settings.get_deaths() {
+                    let _: i32 = mem.deref(&gm.hero_death_count).unwrap_or_default();
+                }
+                if settings.get_steel_soul_death() != SteelSoulDeath::Ignore {
+                    let _: i32 = mem.deref(&pd.permadeath_mode).unwrap_or_default();
+                }
+                if settings.get_timer_mode() == TimerMode::BossPractice {
+                    let _ = settings.get_practice_boss().encountered(&mem, &pd);
+                    let _ = settings.get_practice_boss().defeated(&mem, &pd);
+                }
+                let split_gm_fields: Vec<&str> = state
+                    .split_pointers
+                    .iter()
+                    .filter_map(SplitPointer::gm_field)
+                    .collect();
+                gm.resolve_fields(&mem, &split_gm_fields);
+                let split_pd_fields: Vec<&str> = state
+                    .split_pointers
+                    .iter()
+                    .filter_map(SplitPointer::pd_field)
+                    .collect();
+                pd.resolve_fields(&mem, &split_pd_fields);
                 next_tick().await;
                 asr::print_message("Initialized load removal pointers");
                 next_tick().await;
@@ -437,14 +1486,70 @@ async fn main() {
                             && scene_store.pair().current == MENU_TITLE)
                     {
                         settings.load_update_store_if_unchanged();
+                        state.remap_comparisons(&mut settings);
+                        state.split_pointers = settings.get_split_pointers();
                         ticks_since_gui = 0;
                     }
                     state.update(&settings);
 
                     // TODO: Do something on every tick.
-                    handle_splits(&settings, &mut state, &mem, &gm, &pd, &mut scene_store).await;
-                    load_removal(&mut state, &mem, &gm);
-                    handle_hits(&settings, &mut state, &mem, &gm, &pd);
+                    handle_version(&mut state, &mem, &mut gm, &mut pd);
+                    let tick = TickSnapshot::read(&mem, &gm, &pd, tick_reads(&settings, &state));
+                    if state
+                        .split_pointers
+                        .contains(&SplitPointer::Pd(pd_fields::hero_corpse_scene))
+                    {
+                        scene_store.update_corpse_scene(&tick);
+                    }
+                    handle_diagnostics(&settings, &mut state, &mem, &gm, &pd, &tick);
+                    let trans_now = scene_store.transition_now(&tick);
+                    match settings.get_timer_mode() {
+                        TimerMode::Splits => {
+                            handle_splits(
+                                &settings,
+                                &mut state,
+                                &mem,
+                                &gm,
+                                &pd,
+                                &tick,
+                                &mut scene_store,
+                                trans_now,
+                            )
+                            .await
+                        }
+                        TimerMode::RoomTimer => {
+                            handle_room_timer(&settings, &mut state, &scene_store, trans_now)
+                        }
+                        TimerMode::IlPractice => {
+                            handle_il_practice(
+                                &settings,
+                                &mut state,
+                                &mem,
+                                &gm,
+                                &pd,
+                                &tick,
+                                &mut scene_store,
+                                trans_now,
+                            )
+                            .await
+                        }
+                        TimerMode::BossPractice => handle_boss_practice(
+                            &settings,
+                            &mut state,
+                            &mem,
+                            &pd,
+                            &tick,
+                            &scene_store,
+                            trans_now,
+                        ),
+                    }
+                    load_removal(&settings, &mut state, &mem, &gm, &tick);
+                    handle_hits(&settings, &mut state, &mem, &pd, &tick);
+                    handle_stats(&settings, &mut state, &mem, &pd);
+                    handle_area(&settings, &mut state, &mem, &gm, &pd, &scene_store, &tick);
+                    let died = handle_deaths(&settings, &mut state, &mem, &gm, &tick);
+                    handle_steel_soul(&settings, &mut state, &mem, &pd, &scene_store, died);
+                    handle_profile(&settings, &mut state, &mem, &gm, &pd, &tick);
                     next_tick().await;
                 }
             })
@@ -455,6 +1560,8 @@ async fn main() {
 async fn wait_attach_silksong(gui: &mut Settings, state: &mut AutoSplitterState) -> Process {
     retry(|| {
         gui.load_update_store_if_unchanged();
+        state.remap_comparisons(gui);
+        state.split_pointers = gui.get_split_pointers();
         state.update(gui);
         attach_silksong()
     })
@@ -463,15 +1570,17 @@ async fn wait_attach_silksong(gui: &mut Settings, state: &mut AutoSplitterState)
 
 // --------------------------------------------------------
 
+#[allow(clippy::too_many_arguments)]
 async fn handle_splits(
     settings: &Settings,
     state: &mut AutoSplitterState,
     mem: &Memory<'_>,
     gm: &GameManagerPointers,
     pd: &PlayerDataPointers,
+    tick: &TickSnapshot,
     ss: &mut SceneStore,
+    trans_now: bool,
 ) {
-    let trans_now = ss.transition_now(mem, gm);
     loop {
         match state.timer_state {
             TimerState::NotRunning => {
@@ -479,13 +1588,10 @@ async fn handle_splits(
                 let Some(split) = settings.get_split(0) else {
                     break;
                 };
-                let a = splits::splits(&split, mem, gm, pd, trans_now, ss);
+                let a = splits::splits(&split, mem, gm, pd, tick, trans_now, ss);
                 match a {
                     SplitterAction::Split => {
-                        asr::timer::start();
-                        state.timer_state = TimerState::Running;
-                        state.split_index = Some(0);
-                        state.segment_hits.resize(1, 0);
+                        start_run(state);
                         break;
                     }
                     _ => break,
@@ -497,75 +1603,18 @@ async fn handle_splits(
                 else {
                     break;
                 };
-                let a = splits::splits(&split, mem, gm, pd, trans_now, ss);
+                let a = splits::splits(&split, mem, gm, pd, tick, trans_now, ss);
                 match a {
                     SplitterAction::Reset => {
-                        if settings.get_hit_counter() {
-                            Settings::update_comparison_hits(
-                                &mut state.comparison_hits,
-                                &state.cumulative_hits,
-                            );
-                        }
-                        asr::timer::reset();
-                        state.timer_state = TimerState::NotRunning;
-                        state.split_index = None;
-                        state.segments_splitted.clear();
-                        state.hits = 0;
-                        state.segment_hits.clear();
-                        state.cumulative_hits.clear();
-                        if settings.get_hit_counter() {
-                            asr::timer::set_variable_int("hits", state.hits);
-                            asr::timer::set_variable_int("segment hits", 0);
-                        } else {
-                            asr::timer::set_variable("hits", DASH);
-                            asr::timer::set_variable("segment hits", DASH);
-                        }
-                        state.look_for_teleporting = false;
-                        state.last_game_state = GAME_STATE_INACTIVE;
-                        #[cfg(debug_assertions)]
-                        {
-                            state.last_paused = false;
-                        }
+                        reset_run(settings, state);
                         // no break, allow other actions after a skip or reset
                     }
                     SplitterAction::Skip => {
-                        let old_index = state.split_index.unwrap_or_default();
-                        let old_i = old_index as usize;
-                        asr::timer::skip_split();
-                        let new_i = old_i + 1;
-                        state.split_index = Some(old_index + 1);
-                        state.segments_splitted.push(false);
-                        state.segment_hits.insert(old_i, 0);
-                        if settings.get_hit_counter() {
-                            asr::timer::set_variable_int("segment hits", state.segment_hits[new_i]);
-                            if let Some(c) = state.comparison_hits.get(new_i) {
-                                asr::timer::set_variable_int("comparison hits", *c);
-                                asr::timer::set_variable_int("delta hits", state.hits - c);
-                            } else {
-                                asr::timer::set_variable("comparison hits", DASH);
-                                asr::timer::set_variable("delta hits", DASH);
-                            }
-                        }
+                        skip_segment(settings, state);
                         // no break, allow other actions after a skip or reset
                     }
                     SplitterAction::Split => {
-                        let old_index = state.split_index.unwrap_or_default();
-                        asr::timer::split();
-                        let new_i = old_index as usize + 1;
-                        state.split_index = Some(old_index + 1);
-                        state.segments_splitted.push(true);
-                        state.segment_hits.push(0);
-                        state.cumulative_hits.resize(new_i, state.hits);
-                        if settings.get_hit_counter() {
-                            asr::timer::set_variable_int("segment hits", state.segment_hits[new_i]);
-                            if let Some(c) = state.comparison_hits.get(new_i) {
-                                asr::timer::set_variable_int("comparison hits", *c);
-                                asr::timer::set_variable_int("delta hits", state.hits - c);
-                            } else {
-                                asr::timer::set_variable("comparison hits", DASH);
-                                asr::timer::set_variable("delta hits", DASH);
-                            }
-                        }
+                        split_segment(settings, state);
                         break;
                     }
                     SplitterAction::ManualSplit => {
@@ -576,19 +1625,9 @@ async fn handle_splits(
                             let new_i = old_i + 1;
                             state.split_index = Some(old_index + 1);
                             state.segments_splitted.push(false);
-                            state.segment_hits.insert(old_i, 0);
+                            state.segment_hits.insert(old_i, HitCounts::default());
                             if settings.get_hit_counter() {
-                                asr::timer::set_variable_int(
-                                    "segment hits",
-                                    state.segment_hits[new_i],
-                                );
-                                if let Some(c) = state.comparison_hits.get(new_i) {
-                                    asr::timer::set_variable_int("comparison hits", *c);
-                                    asr::timer::set_variable_int("delta hits", state.hits - c);
-                                } else {
-                                    asr::timer::set_variable("comparison hits", DASH);
-                                    asr::timer::set_variable("delta hits", DASH);
-                                }
+                                state.set_segment_hit_variables(new_i);
                             }
                         }
                         break;
@@ -601,80 +1640,238 @@ async fn handle_splits(
     }
 }
 
-fn load_removal(state: &mut AutoSplitterState, mem: &Memory, gm: &GameManagerPointers) {
-    // only remove loads if timer is running
-    if asr::timer::state() != TimerState::Running {
+/// Detects the game version, and resolves the pointers for it
+/// The fields of the tick snapshot that only some settings need, read when they're needed
+fn tick_reads(settings: &Settings, state: &AutoSplitterState) -> TickReads {
+    TickReads {
+        hits: settings.get_hit_counter(),
+        hero_corpse_scene: settings.get_death_variables()
+            || state
+                .split_pointers
+                .contains(&SplitPointer::Pd(pd_fields::hero_corpse_scene)),
+        play_time: settings.get_game_time_source() == GameTimeSource::PlayTime
+            || settings.get_game_time_drift()
+            || settings.get_profile_change() != ProfileChange::Ignore,
+    }
+}
+
+fn handle_version(
+    state: &mut AutoSplitterState,
+    mem: &Memory,
+    gm: &mut GameManagerPointers,
+    pd: &mut PlayerDataPointers,
+) {
+    // the version can't change without restarting the game, which attaches again
+    if state.game_version.is_some() {
         return;
     }
+    let Some(version) = mem.read_string(&pd.version).filter(|v| !v.is_empty()) else {
+        return;
+    };
+    let revision_break: i32 = mem.deref(&pd.revision_break).unwrap_or_default();
+    asr::print_message(&format!(
+        "game version: {}, revision break: {}",
+        version, revision_break
+    ));
+    asr::timer::set_variable("game version", &version);
+    gm.resolve_alternates(mem);
+    pd.resolve_alternates(mem);
+    state.game_version = Some(version);
+}
 
-    let ui_state: i32 = mem.deref(&gm.ui_state_vanilla).unwrap_or_default();
-    let scene_name = mem.read_string(&gm.scene_name).unwrap_or_default();
-    let next_scene = mem.read_string(&gm.next_scene_name).unwrap_or_default();
+/// Reports the pointers that don't resolve, and the splits that depend on them,
+/// once per attach, after the version is known and gameplay has started
+fn handle_diagnostics(
+    settings: &Settings,
+    state: &mut AutoSplitterState,
+    mem: &Memory,
+    gm: &GameManagerPointers,
+    pd: &PlayerDataPointers,
+    tick: &TickSnapshot,
+) {
+    if state.pointers_diagnosed || state.game_version.is_none() {
+        return;
+    }
+    if tick.game_state != GAME_STATE_PLAYING {
+        return;
+    }
+    state.pointers_diagnosed = true;
 
-    let loading_menu = (scene_name != MENU_TITLE && next_scene.is_empty())
-        || (scene_name != MENU_TITLE && next_scene == MENU_TITLE || (scene_name == QUIT_TO_MENU));
+    let needed = settings.get_needed_pointers();
+    let needed_gm: Vec<&str> = needed.iter().filter_map(SplitPointer::gm_field).collect();
+    let needed_pd: Vec<&str> = needed.iter().filter_map(SplitPointer::pd_field).collect();
+    let broken_gm = gm.broken(mem, &needed_gm);
+    let broken_pd = pd.broken(mem, &needed_pd);
+    let broken: Vec<String> = broken_gm
+        .iter()
+        .map(|f| format!("gm.{}", f))
+        .chain(broken_pd.iter().map(|f| format!("pd.{}", f)))
+        .collect();
+    if broken.is_empty() {
+        asr::print_message("diagnostics: all needed pointers resolve");
+        asr::timer::set_variable("diagnostics", "OK");
+        return;
+    }
+    asr::print_message(&format!(
+        "diagnostics: broken pointers: {}",
+        broken.join(", ")
+    ));
 
-    // TODO: teleporting, look_for_teleporting
+    let mut broken_splits = 0;
+    for split in settings.get_splits() {
+        let needs = splits::split_pointers(&split);
+        if let Some(p) = needs.iter().find(|p| match p {
+            SplitPointer::Gm(f) => broken_gm.contains(f),
+            SplitPointer::Pd(f) => broken_pd.contains(f),
+        }) {
+            broken_splits += 1;
+            asr::print_message(&format!(
+                "diagnostics: warning: split {} depends on broken pointer {}",
+                splits::split_description(&split),
+                p
+            ));
+        }
+    }
+    asr::timer::set_variable(
+        "diagnostics",
+        &format!(
+            "{} broken pointers, {} broken splits",
+            broken.len(),
+            broken_splits
+        ),
+    );
+}
 
-    let game_state: i32 = mem.deref(&gm.game_state).unwrap_or_default();
+fn load_removal(
+    settings: &Settings,
+    state: &mut AutoSplitterState,
+    mem: &Memory,
+    gm: &GameManagerPointers,
+    tick: &TickSnapshot,
+) {
+    if state.split_index != state.segment_loads_index {
+        state.segment_loads = LoadTimes::default();
+        state.segment_loads_index = state.split_index;
+        asr::timer::set_variable("segment loads", &state.segment_loads.to_variable_string());
+    }
 
-    if game_state == GAME_STATE_PLAYING && state.last_game_state == GAME_STATE_MAIN_MENU {
-        state.look_for_teleporting = true;
+    // only remove loads if timer is running
+    if asr::timer::state() != TimerState::Running {
+        state.last_load = None;
+        state.last_game_time_tick = None;
+        state.last_flags_loading = false;
+        state.room_timer.stop_ticks();
+        return;
     }
-    if state.look_for_teleporting
-        && (game_state != GAME_STATE_PLAYING && game_state != GAME_STATE_ENTERING_LEVEL)
-    {
-        state.look_for_teleporting = false;
-    }
-
-    // TODO: hazard_respawning
-    let accepting_input: bool = mem.deref(&gm.accepting_input).unwrap_or_default();
-    let hero_transition_state: i32 = mem.deref(&gm.hero_transition_state).unwrap_or_default();
-    let scene_load_activation_allowed: bool = mem
-        .deref(&gm.scene_load_activation_allowed)
-        .unwrap_or_default();
-    // TODO: tile_map_dirty, uses_scene_transition_routine
-
-    let is_game_time_paused = (state.look_for_teleporting)
-        || ((game_state == GAME_STATE_PLAYING || game_state == GAME_STATE_ENTERING_LEVEL)
-            && ui_state != UI_STATE_PLAYING)
-        || (game_state != GAME_STATE_PLAYING
-            && game_state != GAME_STATE_CUTSCENE
-            && !accepting_input)
-        || ((game_state == GAME_STATE_EXITING_LEVEL && scene_load_activation_allowed)
-            || game_state == GAME_STATE_LOADING)
-        || (hero_transition_state == HERO_TRANSITION_STATE_WAITING_TO_ENTER_LEVEL)
-        || (ui_state != UI_STATE_PLAYING
-            && (loading_menu
-                || (ui_state != UI_STATE_PAUSED
-                    && ui_state != UI_STATE_CUTSCENE
-                    && (!next_scene.is_empty())))
-            && next_scene != scene_name);
-    if is_game_time_paused {
+
+    let signal = settings.get_load_signal();
+    let snapshot = LoadRemovalSnapshot::read(mem, gm, &signal, tick);
+    let last_game_state = state.load_removal.last_game_state;
+    let decision = load_removal::load_removal_step(
+        &settings.get_load_removal(),
+        &signal,
+        &mut state.load_removal,
+        &snapshot,
+    );
+    let is_game_time_paused = decision.is_game_time_paused;
+    let source = settings.get_game_time_source();
+    // the in-game clock sets game time itself, so it stays paused in between
+    if is_game_time_paused || source == GameTimeSource::PlayTime {
         asr::timer::pause_game_time();
     } else {
         asr::timer::resume_game_time();
     }
 
+    let now = Instant::now();
+    if let Some(last) = state.last_game_time_tick.replace(now) {
+        if !is_game_time_paused {
+            state.load_removed_time += now.duration_since(last);
+        }
+    }
+    state.room_timer.tick(now, is_game_time_paused);
+    let drift_variable = settings.get_game_time_drift();
+    let play_time = if source == GameTimeSource::PlayTime || drift_variable {
+        tick.play_time
+    } else {
+        None
+    };
+    if let Some(play_time) = play_time {
+        let start = state.play_time_start.get_or_insert(play_time);
+        // a new file or a different save started over
+        if play_time < *start {
+            *start = play_time;
+        }
+        let in_game_time = asr::time::Duration::seconds_f32(play_time - *start);
+        if source == GameTimeSource::PlayTime {
+            asr::timer::set_game_time(in_game_time);
+        }
+        let drift = format_drift(state.load_removed_time - in_game_time);
+        if drift_variable && drift != state.last_drift {
+            asr::timer::set_variable("game time drift", &drift);
+            state.last_drift = drift;
+        }
+    }
+
+    let disagreement = decision.flags_loading != decision.heuristic_loading;
+    if signal == LoadSignal::CrossChecked && disagreement != state.last_load_disagreement {
+        asr::print_message(&format!(
+            "load signals {}: flags {}, heuristic {}, in transition {}, load duration {}",
+            if disagreement { "disagree" } else { "agree" },
+            decision.flags_loading,
+            decision.heuristic_loading,
+            snapshot.is_in_scene_transition,
+            snapshot.current_load_duration
+        ));
+    }
+    state.last_load_disagreement = disagreement;
+
+    // the game's own timing of each load, shown once the load is over
+    if signal != LoadSignal::Heuristic {
+        if decision.flags_loading {
+            state.load_duration = snapshot.current_load_duration;
+        } else if state.last_flags_loading {
+            asr::timer::set_variable(
+                "last load",
+                &format_duration(asr::time::Duration::seconds_f32(state.load_duration)),
+            );
+        }
+        state.last_flags_loading = decision.flags_loading;
+    }
+
+    if let Some((cause, start)) = state.last_load.take() {
+        let d = now.duration_since(start);
+        state.run_loads.add(cause, d);
+        state.segment_loads.add(cause, d);
+        if !is_game_time_paused {
+            asr::timer::set_variable("loads removed", &state.run_loads.to_variable_string());
+            asr::timer::set_variable("segment loads", &state.segment_loads.to_variable_string());
+        }
+    }
+    if let Some(cause) = decision.cause {
+        state.last_load = Some((cause, now));
+    }
+
     #[cfg(debug_assertions)]
     {
-        if ui_state != state.last_ui_state {
-            asr::print_message(&format!("ui_state: {}", ui_state));
+        if snapshot.ui_state != state.last_ui_state {
+            asr::print_message(&format!("ui_state: {}", snapshot.ui_state));
         }
-        state.last_ui_state = ui_state;
+        state.last_ui_state = snapshot.ui_state;
     }
 
-    if game_state != state.last_game_state {
-        asr::print_message(&format!("game_state: {}", game_state));
+    if snapshot.game_state != last_game_state {
+        asr::print_message(&format!("game_state: {}", snapshot.game_state));
     }
-    state.last_game_state = game_state;
 
     #[cfg(debug_assertions)]
     {
-        if hero_transition_state != state.last_hero_transition_state {
-            asr::print_message(&format!("hero_transition_state: {}", hero_transition_state));
+        if snapshot.hero_transition_state != state.last_hero_transition_state {
+            asr::print_message(&format!(
+                "hero_transition_state: {}",
+                snapshot.hero_transition_state
+            ));
         }
-        state.last_hero_transition_state = hero_transition_state;
+        state.last_hero_transition_state = snapshot.hero_transition_state;
     }
 
     #[cfg(debug_assertions)]
@@ -686,12 +1883,261 @@ fn load_removal(state: &mut AutoSplitterState, mem: &Memory, gm: &GameManagerPoi
     }
 }
 
+fn handle_room_timer(
+    settings: &Settings,
+    state: &mut AutoSplitterState,
+    ss: &SceneStore,
+    trans_now: bool,
+) {
+    if !trans_now {
+        return;
+    }
+    let scenes = ss.pair();
+    let left = state.room_timer.transition(&scenes);
+    if let Some((key, time)) = left {
+        let ms = time.whole_milliseconds() as i64;
+        let best = Settings::update_room_best(&key, ms);
+        asr::timer::set_variable("last room time", &format_duration(time));
+        asr::timer::set_variable(
+            "room best",
+            &format_duration(asr::time::Duration::milliseconds(best)),
+        );
+        #[cfg(debug_assertions)]
+        asr::print_message(&format!("room: {}, {} ms, best {} ms", key, ms, best));
+    }
+
+    let in_room = !state.room_timer.room().is_empty();
+    if in_room {
+        asr::timer::set_variable("current room", state.room_timer.room());
+    } else {
+        asr::timer::set_variable("current room", DASH);
+    }
+
+    match asr::timer::state() {
+        TimerState::Running | TimerState::Paused if !in_room => {
+            // left for the menu, this room can't be finished
+            reset_timer(settings, state);
+        }
+        TimerState::Running | TimerState::Paused => {
+            split_segment(settings, state);
+            // with one segment per room, the split ends the run, so start the next room
+            if asr::timer::state() == TimerState::Ended {
+                reset_timer(settings, state);
+                start_run(state);
+            }
+        }
+        TimerState::Ended => {
+            reset_timer(settings, state);
+            if in_room {
+                start_run(state);
+            }
+        }
+        _ if in_room => start_run(state),
+        _ => (),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn handle_il_practice(
+    settings: &Settings,
+    state: &mut AutoSplitterState,
+    mem: &Memory<'_>,
+    gm: &GameManagerPointers,
+    pd: &PlayerDataPointers,
+    tick: &TickSnapshot,
+    ss: &mut SceneStore,
+    trans_now: bool,
+) {
+    let set_start_room = settings.get_il_set_start_room();
+    // the setting left on from before isn't turning it on
+    let last_set_start_room = state
+        .last_il_set_start_room
+        .replace(set_start_room)
+        .unwrap_or(set_start_room);
+    if set_start_room && !last_set_start_room {
+        let scenes = ss.pair();
+        if is_menu(scenes.current) {
+            asr::print_message("il start room: not set, a menu can't be the start room");
+        } else {
+            Settings::set_il_rooms(scenes.current, scenes.old);
+            asr::timer::set_variable("il start room", scenes.current);
+            asr::print_message(&format!(
+                "il start room: {}, entered from {}",
+                scenes.current, scenes.old
+            ));
+        }
+    }
+
+    let Some((start_room, entry_room)) = Settings::get_il_rooms() else {
+        return;
+    };
+    let scenes = ss.pair();
+    match state.timer_state {
+        TimerState::NotRunning | TimerState::Ended => {
+            // start on entering the start room, from anywhere, including a save or a bench
+            if trans_now && scenes.current == start_room {
+                if state.timer_state == TimerState::Ended {
+                    reset_run(settings, state);
+                }
+                start_run(state);
+            }
+        }
+        TimerState::Running | TimerState::Paused => {
+            let died = tick.health == Some(0);
+            let wrong_way = trans_now
+                && scenes.old == start_room
+                && scenes.current == entry_room
+                && !is_menu(&entry_room);
+            if died || wrong_way || (trans_now && is_menu(scenes.current)) {
+                reset_run(settings, state);
+                return;
+            }
+            handle_splits(settings, state, mem, gm, pd, tick, ss, trans_now).await;
+        }
+        _ => (),
+    }
+}
+
+fn handle_boss_practice(
+    settings: &Settings,
+    state: &mut AutoSplitterState,
+    mem: &Memory<'_>,
+    pd: &PlayerDataPointers,
+    tick: &TickSnapshot,
+    ss: &SceneStore,
+    trans_now: bool,
+) {
+    let boss = settings.get_practice_boss();
+    let (encountered, defeated) = state.boss_flags.update(&boss, mem, pd);
+    let mut record = match state.boss_record.take() {
+        Some((b, record)) if b == boss => record,
+        _ => {
+            let record = Settings::get_boss_record(&boss);
+            record.set_variables();
+            record
+        }
+    };
+    let old_record = record.clone();
+
+    let set_arena = settings.get_boss_set_arena();
+    // the setting left on from before isn't turning it on
+    let last_set_arena = state
+        .last_boss_set_arena
+        .replace(set_arena)
+        .unwrap_or(set_arena);
+    let scenes = ss.pair();
+    if !is_menu(scenes.current) {
+        // the boss is encountered and defeated in its arena,
+        // so a boss encountered before can still be learned from its defeat
+        let learned = record.arena.is_none() && (encountered || defeated);
+        if (set_arena && !last_set_arena) || learned {
+            record.arena = Some(scenes.current.into());
+            asr::print_message(&format!("boss arena: {}", scenes.current));
+        }
+    }
+    match state.timer_state {
+        TimerState::NotRunning | TimerState::Ended => {
+            let entered_arena = trans_now && record.arena.as_deref() == Some(scenes.current);
+            if encountered || entered_arena {
+                if state.timer_state == TimerState::Ended {
+                    reset_run(settings, state);
+                }
+                start_run(state);
+                record.attempts += 1;
+            }
+        }
+        TimerState::Running | TimerState::Paused => {
+            let died = tick.health == Some(0);
+            if defeated {
+                end_run(settings, state);
+                record.kills += 1;
+            } else if died || trans_now {
+                // a transition while fighting leaves the arena
+                reset_run(settings, state);
+            }
+        }
+        _ => (),
+    }
+
+    if record != old_record {
+        Settings::set_boss_record(&boss, &record);
+        record.set_variables();
+    }
+    state.boss_record = Some((boss, record));
+}
+
+fn start_run(state: &mut AutoSplitterState) {
+    asr::timer::start();
+    state.timer_state = TimerState::Running;
+    state.split_index = Some(0);
+    state.segment_hits.resize(1, HitCounts::default());
+}
+
+fn reset_run(settings: &Settings, state: &mut AutoSplitterState) {
+    if settings.get_hit_counter() {
+        if state.timer_state != TimerState::Ended {
+            state.log_hits(settings, "reset");
+        }
+        state.save_comparison_hits(settings);
+    }
+    reset_timer(settings, state);
+}
+
+/// Resets without saving the hits into the comparisons and the run history,
+/// for the room timer, where each room is a run of its own
+fn reset_timer(settings: &Settings, state: &mut AutoSplitterState) {
+    asr::timer::reset();
+    state.timer_state = TimerState::NotRunning;
+    state.split_index = None;
+    state.clear_run(settings);
+}
+
+fn skip_segment(settings: &Settings, state: &mut AutoSplitterState) {
+    let old_index = state.split_index.unwrap_or_default();
+    let old_i = old_index as usize;
+    asr::timer::skip_split();
+    let new_i = old_i + 1;
+    state.split_index = Some(old_index + 1);
+    state.segments_splitted.push(false);
+    state.segment_hits.insert(old_i, HitCounts::default());
+    if settings.get_hit_counter() {
+        state.set_segment_hit_variables(new_i);
+    }
+}
+
+fn split_segment(settings: &Settings, state: &mut AutoSplitterState) {
+    let old_index = state.split_index.unwrap_or_default();
+    asr::timer::split();
+    let new_i = old_index as usize + 1;
+    state.split_index = Some(old_index + 1);
+    state.split_made = true;
+    state.segments_splitted.push(true);
+    state.segment_hits.push(HitCounts::default());
+    state.cumulative_hits.resize(new_i, state.hits);
+    if settings.get_hit_counter() {
+        state.set_segment_hit_variables(new_i);
+    }
+}
+
+/// Skips to the last split and splits it,
+/// marking the run incomplete when there were splits to skip
+fn end_run(settings: &Settings, state: &mut AutoSplitterState) {
+    let last = settings.get_splits_len().saturating_sub(1) as u64;
+    while state.split_index.unwrap_or_default() + 1 < last {
+        skip_segment(settings, state);
+        state.run_incomplete = true;
+    }
+    split_segment(settings, state);
+}
+
+// --------------------------------------------------------
+
 fn handle_hits(
     settings: &Settings,
     state: &mut AutoSplitterState,
     mem: &Memory,
-    gm: &GameManagerPointers,
     pd: &PlayerDataPointers,
+    tick: &TickSnapshot,
 ) {
     // only count hits if hit counter is true
     if !settings.get_hit_counter() {
@@ -699,56 +2145,328 @@ fn handle_hits(
     }
     // only count hits if timer is running
     if asr::timer::state() != TimerState::Running {
+        state.last_damage = None;
         return;
     }
 
-    let recoil: bool = mem.deref(&gm.hero_recoil_frozen).unwrap_or_default();
-    if !state.last_recoil && recoil {
-        add_hit(state);
-        #[cfg(debug_assertions)]
-        asr::print_message(&format!("hit: {}, from recoil", state.hits));
-    }
-    state.last_recoil = recoil;
+    let recoil = tick.hero_recoil_frozen;
+    let hazard = tick.hazard_death;
+    let health_0 = tick.health == Some(0) && tick.game_state == GAME_STATE_PLAYING;
 
-    let hazard: bool = mem.deref(&gm.hazard_death).unwrap_or_default();
-    if !state.last_hazard && hazard {
-        add_hit(state);
-        #[cfg(debug_assertions)]
-        asr::print_message(&format!("hit: {}, from hazard", state.hits));
+    match settings.get_hit_mode() {
+        HitMode::Recoil => {
+            if !state.last_recoil && recoil {
+                add_hit(settings, state, HitCause::Enemy);
+                #[cfg(debug_assertions)]
+                asr::print_message(&format!("hit: {}, from recoil", state.hits.total()));
+            }
+            if !state.last_hazard && hazard {
+                add_hit(settings, state, HitCause::Hazard);
+                #[cfg(debug_assertions)]
+                asr::print_message(&format!("hit: {}, from hazard", state.hits.total()));
+            }
+            if !state.last_health_0 && health_0 {
+                add_hit(settings, state, HitCause::Death);
+                #[cfg(debug_assertions)]
+                asr::print_message(&format!("hit: {}, from heath 0", state.hits.total()));
+            }
+            state.last_damage = None;
+        }
+        HitMode::Damage => {
+            let damage = DamageSnapshot::read(mem, pd, tick.health);
+            if let (Some(last), Some(now)) = (&state.last_damage, &damage) {
+                if let Some(masks) = hits::damage_taken(last, now) {
+                    let cause = if health_0 {
+                        HitCause::Death
+                    } else if hazard {
+                        HitCause::Hazard
+                    } else {
+                        HitCause::Enemy
+                    };
+                    // before the hit, so that a hitless reset logs the masks lost to it
+                    state.masks_lost += masks;
+                    asr::timer::set_variable_int("masks lost", state.masks_lost);
+                    add_hit(settings, state, cause);
+                    #[cfg(debug_assertions)]
+                    asr::print_message(&format!(
+                        "hit: {}, from damage, {:?}, masks lost: {}",
+                        state.hits.total(),
+                        cause,
+                        masks
+                    ));
+                }
+            }
+            state.last_damage = damage;
+        }
     }
+    state.last_recoil = recoil;
     state.last_hazard = hazard;
-
-    let maybe_health: Option<i32> = mem.deref(&pd.health).ok();
-    let game_state: i32 = mem.deref(&gm.game_state).unwrap_or_default();
-    let health_0 = maybe_health == Some(0) && game_state == GAME_STATE_PLAYING;
-    if !state.last_health_0 && health_0 {
-        add_hit(state);
-        #[cfg(debug_assertions)]
-        asr::print_message(&format!("hit: {}, from heath 0", state.hits));
-    }
     state.last_health_0 = health_0;
 
     #[cfg(debug_assertions)]
     {
-        if maybe_health != state.last_health {
-            asr::print_message(&format!("health: {:?}", maybe_health));
+        if tick.health != state.last_health {
+            asr::print_message(&format!("health: {:?}", tick.health));
         }
-        state.last_health = maybe_health;
+        state.last_health = tick.health;
     }
 }
 
-fn add_hit(state: &mut AutoSplitterState) {
-    state.hits += 1;
-    asr::timer::set_variable_int("hits", state.hits);
+fn add_hit(settings: &Settings, state: &mut AutoSplitterState, cause: HitCause) {
+    // a hitless reset from an earlier hit this tick ends the run
+    if state.timer_state == TimerState::NotRunning {
+        return;
+    }
+    state.hits.add_hit(cause);
+    asr::timer::set_variable_int("hits", state.hits.total());
+    state.set_hit_cause_variables();
     let i = state.split_index.unwrap_or_default() as usize;
-    state.segment_hits.resize(i + 1, 0);
-    state.segment_hits[i] += 1;
-    asr::timer::set_variable_int("segment hits", state.segment_hits[i]);
-    if let Some(c) = state.comparison_hits.get(i) {
-        asr::timer::set_variable_int("delta hits", state.hits - c);
+    state.segment_hits.resize(i + 1, HitCounts::default());
+    state.segment_hits[i].add_hit(cause);
+    asr::timer::set_variable_int("segment hits", state.segment_hits[i].total());
+    if let Some(Some(c)) = state.comparison_hits.get(i) {
+        asr::timer::set_variable_int("delta hits", state.hits.total() - c);
     } else {
         asr::timer::set_variable("delta hits", DASH);
     }
+    hitless_hit(settings, state);
+}
+
+/// Fails a hitless run on the first hit outside of an exempt segment
+fn hitless_hit(settings: &Settings, state: &mut AutoSplitterState) {
+    if state.hitless_failed {
+        return;
+    }
+    let i = state.split_index.unwrap_or_default();
+    if settings.get_hitless_segment(i) == HitlessSegment::Exempt {
+        return;
+    }
+    match settings.get_hitless() {
+        HitlessMode::Off => return,
+        HitlessMode::Reset => {
+            asr::print_message("hitless: FAILED, resetting");
+            reset_run(settings, state);
+            return;
+        }
+        HitlessMode::FailedSplit => {
+            let splits = settings.get_splits();
+            let failed = splits
+                .iter()
+                .skip(i as usize + 1)
+                .position(|s| *s == splits::Split::HitlessFailed);
+            if let Some(f) = failed {
+                asr::print_message("hitless: FAILED, splitting");
+                for _ in 0..f {
+                    skip_segment(settings, state);
+                }
+                split_segment(settings, state);
+            } else {
+                asr::print_message("hitless: FAILED, but there is no Hitless Failed split");
+            }
+        }
+        HitlessMode::Variable => (),
+    }
+    state.hitless_failed = true;
+    asr::timer::set_variable("hitless", "FAILED");
+}
+
+fn handle_stats(
+    settings: &Settings,
+    state: &mut AutoSplitterState,
+    mem: &Memory,
+    pd: &PlayerDataPointers,
+) {
+    for stat in PlayerStat::ALL {
+        if !settings.get_stat(stat) {
+            state.stat_variables.clear(stat);
+        } else if let Some(value) = stat.read(mem, pd) {
+            state.stat_variables.update(stat, value);
+        }
+    }
+    if !settings.get_stat_completion() {
+        state.stat_variables.clear_completion();
+    } else if let Ok(completion) = mem.deref(&pd.completion_percentage) {
+        state.stat_variables.update_completion(completion);
+    }
+}
+
+fn handle_area(
+    settings: &Settings,
+    state: &mut AutoSplitterState,
+    mem: &Memory,
+    gm: &GameManagerPointers,
+    pd: &PlayerDataPointers,
+    ss: &SceneStore,
+    tick: &TickSnapshot,
+) {
+    if !settings.get_area_variable() {
+        state.area.clear();
+        return;
+    }
+    let playing = tick.game_state == GAME_STATE_PLAYING;
+    if let Some(area) = state.area.update(ss.pair().current, playing, mem, gm, pd) {
+        if area.is_empty() {
+            asr::timer::set_variable("area", DASH);
+        } else {
+            asr::timer::set_variable("area", area);
+        }
+        #[cfg(debug_assertions)]
+        asr::print_message(&format!(
+            "area: {}, mapZone: {:?}",
+            area,
+            mem.deref::<i32, 3>(&pd.map_zone).ok()
+        ));
+    }
+}
+
+/// Returns whether the hero died this tick
+fn handle_deaths(
+    settings: &Settings,
+    state: &mut AutoSplitterState,
+    mem: &Memory,
+    gm: &GameManagerPointers,
+    tick: &TickSnapshot,
+) -> bool {
+    if !settings.get_deaths() {
+        return false;
+    }
+    let variables = settings.get_death_variables();
+    if state.split_index != state.segment_deaths_index {
+        state.segment_deaths = 0;
+        state.segment_deaths_index = state.split_index;
+        if variables {
+            asr::timer::set_variable_int("segment deaths", state.segment_deaths);
+        }
+    }
+
+    let timer_state = asr::timer::state();
+    let running = timer_state == TimerState::Running || timer_state == TimerState::Paused;
+    let Ok(count) = mem.deref::<i32, 2>(&gm.hero_death_count) else {
+        return false;
+    };
+    let died = running && state.last_hero_death_count.is_some_and(|last| last < count);
+    match state.last_hero_death_count {
+        Some(last) if died => {
+            let new_deaths = (count - last) as i64;
+            state.deaths += new_deaths;
+            state.segment_deaths += new_deaths;
+            if variables {
+                asr::timer::set_variable_int("deaths", state.deaths);
+                asr::timer::set_variable_int("segment deaths", state.segment_deaths);
+            }
+        }
+        _ => (),
+    }
+    state.last_hero_death_count = Some(count);
+    if !variables {
+        return died;
+    }
+
+    // the cocoon is left behind after the death, record where once it's there:
+    // when the cocoon scene changed, or when it's in the same scene as an earlier cocoon,
+    // once the hero respawned in another scene
+    let corpse_scene = tick.hero_corpse_scene.clone().unwrap_or_default();
+    let before = core::mem::replace(&mut state.last_corpse_scene, corpse_scene.clone());
+    if died {
+        state.death_scene_pending = Some((before, tick.scene_name.clone()));
+    }
+    if let Some((before, death_scene)) = &state.death_scene_pending {
+        if !corpse_scene.is_empty() && (corpse_scene != *before || tick.scene_name != *death_scene)
+        {
+            asr::print_message(&format!("death: {}, in {}", state.deaths, corpse_scene));
+            asr::timer::set_variable("death scene", &corpse_scene);
+            state.death_scenes.push(corpse_scene);
+            state.death_scene_pending = None;
+        }
+    }
+    died
+}
+
+fn handle_steel_soul(
+    settings: &Settings,
+    state: &mut AutoSplitterState,
+    mem: &Memory,
+    pd: &PlayerDataPointers,
+    ss: &SceneStore,
+    died: bool,
+) {
+    if settings.get_steel_soul_death() == SteelSoulDeath::Ignore {
+        return;
+    }
+    let Ok(mode) = mem.deref::<i32, 3>(&pd.permadeath_mode) else {
+        return;
+    };
+    let last_mode = core::mem::replace(&mut state.last_permadeath_mode, mode);
+    let timer_state = asr::timer::state();
+    if timer_state != TimerState::Running && timer_state != TimerState::Paused {
+        return;
+    }
+    if !state.steel_soul_dead && steel_soul::steel_soul_died(last_mode, mode, died) {
+        state.steel_soul_dead = true;
+        asr::print_message("steel soul: dead");
+        match settings.get_steel_soul_death() {
+            SteelSoulDeath::EndRun => end_run(settings, state),
+            SteelSoulDeath::Reset => reset_run(settings, state),
+            SteelSoulDeath::KeepTiming | SteelSoulDeath::Ignore => (),
+        }
+    } else if state.steel_soul_dead
+        && settings.get_steel_soul_death() == SteelSoulDeath::KeepTiming
+        && steel_soul::is_game_over(ss.pair().current)
+    {
+        asr::print_message("steel soul: game over");
+        end_run(settings, state);
+    }
+}
+
+fn handle_profile(
+    settings: &Settings,
+    state: &mut AutoSplitterState,
+    mem: &Memory,
+    gm: &GameManagerPointers,
+    pd: &PlayerDataPointers,
+    tick: &TickSnapshot,
+) {
+    if settings.get_profile_change() == ProfileChange::Ignore {
+        return;
+    }
+    let timer_state = asr::timer::state();
+    if timer_state != TimerState::Running && timer_state != TimerState::Paused {
+        return;
+    }
+    if tick.game_state != GAME_STATE_PLAYING {
+        return;
+    }
+    let (Ok(gm_profile), Ok(pd_profile), Some(play_time)) = (
+        mem.deref::<i32, 2>(&gm.profile_id),
+        mem.deref::<i32, 3>(&pd.profile_id),
+        tick.play_time,
+    ) else {
+        return;
+    };
+    let last_slot = state.profile.slot();
+    let Some(event) = state.profile.update(gm_profile, pd_profile, play_time) else {
+        if let Some(slot) = state.profile.slot().filter(|s| Some(*s) != last_slot) {
+            asr::timer::set_variable_int("save slot", slot);
+        }
+        return;
+    };
+    let message = match event {
+        ProfileEvent::SlotChanged { from, to } => {
+            format!("save slot changed from {} to {}", from, to)
+        }
+        ProfileEvent::FreshFile { slot } => format!("fresh file in save slot {}", slot),
+    };
+    match settings.get_profile_change() {
+        ProfileChange::Warn => {
+            asr::print_message(&format!("warning: {}", message));
+            asr::timer::set_variable("save slot", &message);
+        }
+        ProfileChange::Reset => {
+            asr::print_message(&message);
+            reset_run(settings, state);
+        }
+        ProfileChange::Ignore => (),
+    }
 }
 
 // --------------------------------------------------------
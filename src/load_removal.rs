@@ -0,0 +1,421 @@
+use alloc::{format, string::String};
+use asr::{settings::Gui, time::Duration};
+use ugly_widget::{
+    radio_button::{options_str, RadioButtonOptions},
+    store::StoreWidget,
+};
+
+use crate::silksong_memory::{
+    GameManagerPointers, Memory, TickSnapshot, GAME_STATE_CUTSCENE, GAME_STATE_ENTERING_LEVEL,
+    GAME_STATE_EXITING_LEVEL, GAME_STATE_INACTIVE, GAME_STATE_LOADING, GAME_STATE_MAIN_MENU,
+    GAME_STATE_PLAYING, HERO_TRANSITION_STATE_WAITING_TO_ENTER_LEVEL, MENU_TITLE, QUIT_TO_MENU,
+    UI_STATE_CUTSCENE, UI_STATE_PAUSED, UI_STATE_PLAYING,
+};
+
+// --------------------------------------------------------
+
+#[derive(Clone, Debug, Default, Eq, Gui, Ord, PartialEq, PartialOrd, RadioButtonOptions)]
+pub enum LoadRemovalRules {
+    /// Standard (leaderboard)
+    ///
+    /// Pauses game time during loads, menus, and teleports,
+    /// following the current leaderboard rules
+    #[default]
+    Standard,
+    /// Strict (loads only, pause menu counts)
+    ///
+    /// Pauses game time during loads,
+    /// but time spent in the pause menu still counts
+    Strict,
+    /// None (RTA)
+    ///
+    /// Never pauses game time
+    Rta,
+}
+
+impl StoreWidget for LoadRemovalRules {
+    fn insert_into(&self, settings_map: &asr::settings::Map, key: &str) -> bool {
+        let new_s = options_str(self);
+        if settings_map
+            .get(key)
+            .is_some_and(|old_v| old_v.get_string().is_some_and(|old_s| old_s == new_s))
+        {
+            return false;
+        }
+        settings_map.insert(key, new_s);
+        true
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, Gui, Ord, PartialEq, PartialOrd, RadioButtonOptions)]
+pub enum LoadSignal {
+    /// Heuristic
+    ///
+    /// Detects scene loads from the game state and the hero's transition state
+    #[default]
+    Heuristic,
+    /// Loading Flags
+    ///
+    /// Detects scene loads from the GameManager's isLoading,
+    /// IsInSceneTransition, and IsWaitingForSceneReady flags,
+    /// and shows the game's own duration of the last load as a variable
+    LoadingFlags,
+    /// Loading Flags, Cross-checked
+    ///
+    /// Uses the loading flags,
+    /// and logs whenever they disagree with the heuristic
+    CrossChecked,
+}
+
+impl StoreWidget for LoadSignal {
+    fn insert_into(&self, settings_map: &asr::settings::Map, key: &str) -> bool {
+        let new_s = options_str(self);
+        if settings_map
+            .get(key)
+            .is_some_and(|old_v| old_v.get_string().is_some_and(|old_s| old_s == new_s))
+        {
+            return false;
+        }
+        settings_map.insert(key, new_s);
+        true
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, Gui, Ord, PartialEq, PartialOrd, RadioButtonOptions)]
+pub enum GameTimeSource {
+    /// Load Removal
+    ///
+    /// Game time is real time, paused according to the load removal rules
+    #[default]
+    LoadRemoval,
+    /// In-Game Clock
+    ///
+    /// Game time is the save file's play time, counted from the start of the run
+    PlayTime,
+}
+
+impl StoreWidget for GameTimeSource {
+    fn insert_into(&self, settings_map: &asr::settings::Map, key: &str) -> bool {
+        let new_s = options_str(self);
+        if settings_map
+            .get(key)
+            .is_some_and(|old_v| old_v.get_string().is_some_and(|old_s| old_s == new_s))
+        {
+            return false;
+        }
+        settings_map.insert(key, new_s);
+        true
+    }
+}
+
+// --------------------------------------------------------
+
+/// Loading a save from the main menu,
+/// until the game is neither playing nor entering a level
+pub fn teleporting(look_for_teleporting: bool) -> bool {
+    look_for_teleporting
+}
+
+/// In gameplay, but the UI is in a state other than playing,
+/// including the pause menu
+pub fn ui_not_playing(game_state: i32, ui_state: i32) -> bool {
+    (game_state == GAME_STATE_PLAYING || game_state == GAME_STATE_ENTERING_LEVEL)
+        && ui_state != UI_STATE_PLAYING
+}
+
+/// In gameplay, with the pause menu open
+pub fn pause_menu(game_state: i32, ui_state: i32) -> bool {
+    (game_state == GAME_STATE_PLAYING || game_state == GAME_STATE_ENTERING_LEVEL)
+        && ui_state == UI_STATE_PAUSED
+}
+
+/// Outside of gameplay and cutscenes, and not accepting input
+pub fn input_blocked(game_state: i32, accepting_input: bool) -> bool {
+    game_state != GAME_STATE_PLAYING && game_state != GAME_STATE_CUTSCENE && !accepting_input
+}
+
+/// Loading the next scene
+pub fn scene_loading(game_state: i32, scene_load_activation_allowed: bool) -> bool {
+    (game_state == GAME_STATE_EXITING_LEVEL && scene_load_activation_allowed)
+        || game_state == GAME_STATE_LOADING
+}
+
+/// The hero is waiting to enter the next level
+pub fn waiting_to_enter_level(hero_transition_state: i32) -> bool {
+    hero_transition_state == HERO_TRANSITION_STATE_WAITING_TO_ENTER_LEVEL
+}
+
+/// Loading the next scene, according to the GameManager's flags
+pub fn loading_flags(
+    is_loading: bool,
+    is_in_scene_transition: bool,
+    is_waiting_for_scene_ready: bool,
+) -> bool {
+    is_loading || (is_in_scene_transition && is_waiting_for_scene_ready)
+}
+
+/// Loading into or out of the main menu,
+/// or loading a scene while the UI is not playing
+pub fn menu_loading(ui_state: i32, scene_name: &str, next_scene: &str) -> bool {
+    let loading_menu = (scene_name != MENU_TITLE && next_scene.is_empty())
+        || (scene_name != MENU_TITLE && next_scene == MENU_TITLE || (scene_name == QUIT_TO_MENU));
+    ui_state != UI_STATE_PLAYING
+        && (loading_menu
+            || (ui_state != UI_STATE_PAUSED
+                && ui_state != UI_STATE_CUTSCENE
+                && (!next_scene.is_empty())))
+        && next_scene != scene_name
+}
+
+// --------------------------------------------------------
+
+/// Everything load removal reads from the game on one tick
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LoadRemovalSnapshot {
+    pub game_state: i32,
+    pub ui_state: i32,
+    pub scene_name: String,
+    pub next_scene: String,
+    pub accepting_input: bool,
+    pub hero_transition_state: i32,
+    pub scene_load_activation_allowed: bool,
+    pub hazard_respawning: bool,
+    pub is_loading: bool,
+    pub is_in_scene_transition: bool,
+    pub is_waiting_for_scene_ready: bool,
+    /// How long the game has spent on the current scene load, in seconds
+    pub current_load_duration: f32,
+}
+
+impl LoadRemovalSnapshot {
+    /// Reads a snapshot from the game, on top of the fields already in the tick snapshot.
+    /// The loading flags and the load duration are only read when the load signal uses them.
+    pub fn read(
+        mem: &Memory,
+        gm: &GameManagerPointers,
+        signal: &LoadSignal,
+        tick: &TickSnapshot,
+    ) -> Self {
+        let uses_flags = *signal != LoadSignal::Heuristic;
+        LoadRemovalSnapshot {
+            game_state: tick.game_state,
+            ui_state: tick.ui_state,
+            scene_name: tick.scene_name.clone(),
+            next_scene: tick.next_scene_name.clone(),
+            accepting_input: mem.deref(&gm.accepting_input).unwrap_or_default(),
+            hero_transition_state: mem.deref(&gm.hero_transition_state).unwrap_or_default(),
+            scene_load_activation_allowed: tick.scene_load_activation_allowed,
+            hazard_respawning: mem.deref(&gm.hazard_respawning).unwrap_or_default(),
+            is_loading: uses_flags && mem.deref(&gm.is_loading).unwrap_or_default(),
+            is_in_scene_transition: uses_flags
+                && mem.deref(&gm.is_in_scene_transition).unwrap_or_default(),
+            is_waiting_for_scene_ready: uses_flags
+                && mem
+                    .deref(&gm.is_waiting_for_scene_ready)
+                    .unwrap_or_default(),
+            current_load_duration: if uses_flags {
+                mem.deref(&gm.current_load_duration).unwrap_or_default()
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+/// What load removal remembers from one tick to the next
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoadRemovalState {
+    pub look_for_teleporting: bool,
+    pub last_game_state: i32,
+}
+
+impl LoadRemovalState {
+    pub fn new() -> LoadRemovalState {
+        LoadRemovalState {
+            look_for_teleporting: false,
+            last_game_state: GAME_STATE_INACTIVE,
+        }
+    }
+}
+
+impl Default for LoadRemovalState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// What load removal decided on one tick
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoadRemovalDecision {
+    pub is_game_time_paused: bool,
+    /// `Some` exactly when game time is paused
+    pub cause: Option<LoadCause>,
+    /// Whether the heuristic detects a scene load
+    pub heuristic_loading: bool,
+    /// Whether the loading flags detect a scene load.
+    /// Only meaningful when the load signal reads the loading flags.
+    pub flags_loading: bool,
+}
+
+/// Decides whether game time should be paused on this tick,
+/// without reading from the game or touching the timer.
+pub fn load_removal_step(
+    rules: &LoadRemovalRules,
+    signal: &LoadSignal,
+    state: &mut LoadRemovalState,
+    s: &LoadRemovalSnapshot,
+) -> LoadRemovalDecision {
+    if s.game_state == GAME_STATE_PLAYING && state.last_game_state == GAME_STATE_MAIN_MENU {
+        state.look_for_teleporting = true;
+    }
+    if state.look_for_teleporting
+        && (s.game_state != GAME_STATE_PLAYING && s.game_state != GAME_STATE_ENTERING_LEVEL)
+    {
+        state.look_for_teleporting = false;
+    }
+    state.last_game_state = s.game_state;
+
+    // TODO: tile_map_dirty, uses_scene_transition_routine
+    let heuristic_loading = scene_loading(s.game_state, s.scene_load_activation_allowed)
+        || waiting_to_enter_level(s.hero_transition_state);
+    let flags_loading = loading_flags(
+        s.is_loading,
+        s.is_in_scene_transition,
+        s.is_waiting_for_scene_ready,
+    );
+    let loading = match signal {
+        LoadSignal::Heuristic => heuristic_loading,
+        LoadSignal::LoadingFlags | LoadSignal::CrossChecked => flags_loading,
+    };
+
+    let is_game_time_paused = match rules {
+        LoadRemovalRules::Standard => {
+            teleporting(state.look_for_teleporting)
+                || ui_not_playing(s.game_state, s.ui_state)
+                || input_blocked(s.game_state, s.accepting_input)
+                || loading
+                || menu_loading(s.ui_state, &s.scene_name, &s.next_scene)
+        }
+        LoadRemovalRules::Strict => {
+            teleporting(state.look_for_teleporting)
+                || (ui_not_playing(s.game_state, s.ui_state)
+                    && !pause_menu(s.game_state, s.ui_state))
+                || input_blocked(s.game_state, s.accepting_input)
+                || loading
+                || (menu_loading(s.ui_state, &s.scene_name, &s.next_scene)
+                    && !pause_menu(s.game_state, s.ui_state))
+        }
+        LoadRemovalRules::Rta => false,
+    };
+
+    LoadRemovalDecision {
+        is_game_time_paused,
+        cause: is_game_time_paused.then(|| {
+            load_cause(
+                state.look_for_teleporting,
+                s.hazard_respawning,
+                s.game_state,
+                s.ui_state,
+                &s.scene_name,
+                &s.next_scene,
+            )
+        }),
+        heuristic_loading,
+        flags_loading,
+    }
+}
+
+// --------------------------------------------------------
+
+/// Why game time is paused, for load time statistics
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LoadCause {
+    SceneLoad,
+    Menu,
+    Teleport,
+    HazardRespawn,
+}
+
+/// Decides the cause of a pause, assuming game time is paused.
+/// Teleports take priority over hazard respawns,
+/// which take priority over menus, and anything else is a scene load.
+pub fn load_cause(
+    look_for_teleporting: bool,
+    hazard_respawning: bool,
+    game_state: i32,
+    ui_state: i32,
+    scene_name: &str,
+    next_scene: &str,
+) -> LoadCause {
+    if teleporting(look_for_teleporting) {
+        LoadCause::Teleport
+    } else if hazard_respawning {
+        LoadCause::HazardRespawn
+    } else if game_state == GAME_STATE_MAIN_MENU
+        || ui_not_playing(game_state, ui_state)
+        || menu_loading(ui_state, scene_name, next_scene)
+    {
+        LoadCause::Menu
+    } else {
+        LoadCause::SceneLoad
+    }
+}
+
+/// Amounts of time removed by load removal, by cause
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LoadTimes {
+    pub scene_load: Duration,
+    pub menu: Duration,
+    pub teleport: Duration,
+    pub hazard_respawn: Duration,
+}
+
+impl LoadTimes {
+    pub fn add(&mut self, cause: LoadCause, d: Duration) {
+        match cause {
+            LoadCause::SceneLoad => self.scene_load += d,
+            LoadCause::Menu => self.menu += d,
+            LoadCause::Teleport => self.teleport += d,
+            LoadCause::HazardRespawn => self.hazard_respawn += d,
+        }
+    }
+
+    pub fn total(&self) -> Duration {
+        self.scene_load + self.menu + self.teleport + self.hazard_respawn
+    }
+
+    /// The total followed by the breakdown by cause,
+    /// for use as a timer variable
+    pub fn to_variable_string(&self) -> String {
+        format!(
+            "{} (scene load {}, menu {}, teleport {}, hazard respawn {})",
+            format_duration(self.total()),
+            format_duration(self.scene_load),
+            format_duration(self.menu),
+            format_duration(self.teleport),
+            format_duration(self.hazard_respawn),
+        )
+    }
+}
+
+/// Formats the drift of load removal from the in-game clock as signed seconds,
+/// positive when load removal is ahead
+pub fn format_drift(d: Duration) -> String {
+    format!("{:+.1} s", d.as_seconds_f32())
+}
+
+/// Formats a duration as `m:ss.mmm`, or `h:mm:ss.mmm` past an hour
+pub fn format_duration(d: Duration) -> String {
+    let ms = d.whole_milliseconds().max(0);
+    let (h, m, s, ms) = (
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1_000 % 60,
+        ms % 1_000,
+    );
+    if h > 0 {
+        format!("{}:{:02}:{:02}.{:03}", h, m, s, ms)
+    } else {
+        format!("{}:{:02}.{:03}", m, s, ms)
+    }
+}
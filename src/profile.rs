@@ -0,0 +1,98 @@
+use asr::settings::Gui;
+use ugly_widget::{
+    radio_button::{options_str, RadioButtonOptions},
+    store::StoreWidget,
+};
+
+// --------------------------------------------------------
+
+/// How far the play time has to drop, in seconds,
+/// for the save in the same slot to count as a fresh file
+const FRESH_FILE_PLAY_TIME_DROP: f32 = 60.0;
+
+#[derive(Clone, Debug, Default, Eq, Gui, Ord, PartialEq, PartialOrd, RadioButtonOptions)]
+pub enum ProfileChange {
+    /// Warn
+    ///
+    /// Logs the change and shows it in the save slot variable
+    #[default]
+    Warn,
+    /// Reset
+    ///
+    /// Resets the timer when a different save slot or a fresh file is loaded
+    Reset,
+    /// Ignore
+    ///
+    /// Keeps timing without a warning, and doesn't read the save slot
+    Ignore,
+}
+
+impl StoreWidget for ProfileChange {
+    fn insert_into(&self, settings_map: &asr::settings::Map, key: &str) -> bool {
+        let new_s = options_str(self);
+        if settings_map
+            .get(key)
+            .is_some_and(|old_v| old_v.get_string().is_some_and(|old_s| old_s == new_s))
+        {
+            return false;
+        }
+        settings_map.insert(key, new_s);
+        true
+    }
+}
+
+/// A change of save during a run
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProfileEvent {
+    /// A save from a different slot was loaded
+    SlotChanged { from: i32, to: i32 },
+    /// A fresh file was created in the same slot
+    FreshFile { slot: i32 },
+}
+
+/// The save slot of the current run
+#[derive(Debug, Default)]
+pub struct ProfileTracker {
+    slot: Option<i32>,
+    max_play_time: f32,
+}
+
+impl ProfileTracker {
+    /// The slot the run is on, once a save has been loaded
+    pub fn slot(&self) -> Option<i32> {
+        self.slot
+    }
+
+    /// Updates from the GameManager's and the PlayerData's profile IDs,
+    /// which only agree once a save has been loaded, and from the play time.
+    /// Only call while playing, since the PlayerData can be stale on the menu.
+    pub fn update(
+        &mut self,
+        gm_profile: i32,
+        pd_profile: i32,
+        play_time: f32,
+    ) -> Option<ProfileEvent> {
+        if gm_profile <= 0 || pd_profile != gm_profile {
+            return None;
+        }
+        let Some(slot) = self.slot else {
+            self.slot = Some(gm_profile);
+            self.max_play_time = play_time;
+            return None;
+        };
+        if slot != gm_profile {
+            self.slot = Some(gm_profile);
+            self.max_play_time = play_time;
+            return Some(ProfileEvent::SlotChanged {
+                from: slot,
+                to: gm_profile,
+            });
+        }
+        if play_time + FRESH_FILE_PLAY_TIME_DROP < self.max_play_time {
+            self.max_play_time = play_time;
+            return Some(ProfileEvent::FreshFile { slot });
+        }
+        self.max_play_time = self.max_play_time.max(play_time);
+        None
+    }
+}
@@ -0,0 +1,73 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use asr::{time::Duration, time_util::Instant, watcher::Pair};
+
+use crate::silksong_memory::is_menu;
+
+// --------------------------------------------------------
+
+/// The number of room bests kept, dropping the least recently set past it
+pub const MAX_ROOM_BESTS: usize = 1000;
+
+/// The key for a room's best time, from the room and the scene it was left to
+pub fn room_key(room: &str, next: &str) -> String {
+    format!("{} -> {}", room, next)
+}
+
+/// The keys of the room bests to keep after setting the best for `key`,
+/// from least to most recently set, dropping the least recently set past `MAX_ROOM_BESTS`
+pub fn room_best_keys(old_keys: &[String], key: &str) -> Vec<String> {
+    let mut keys: Vec<String> = old_keys.iter().filter(|k| *k != key).cloned().collect();
+    keys.push(key.to_string());
+    let dropped = keys.len().saturating_sub(MAX_ROOM_BESTS);
+    keys.drain(0..dropped);
+    keys
+}
+
+/// Timing for the room timer mode, in game time with loads removed
+#[derive(Default)]
+pub struct RoomTimer {
+    room: String,
+    time: Option<Duration>,
+    last_tick: Option<Instant>,
+}
+
+impl RoomTimer {
+    /// The room being timed, empty when there is none
+    pub fn room(&self) -> &str {
+        &self.room
+    }
+
+    /// Adds the time since the last tick to the room being timed, unless game time is paused
+    pub fn tick(&mut self, now: Instant, is_game_time_paused: bool) {
+        let last = self.last_tick.replace(now);
+        if let (Some(time), Some(last)) = (&mut self.time, last) {
+            if !is_game_time_paused {
+                *time += now.duration_since(last);
+            }
+        }
+    }
+
+    /// The timer isn't running, so no time passes until the next tick
+    pub fn stop_ticks(&mut self) {
+        self.last_tick = None;
+    }
+
+    /// Starts timing the room entered by a transition, ending the room that was left.
+    /// Returns the key and time of the room that was left, if it was timed
+    pub fn transition(&mut self, scenes: &Pair<&str>) -> Option<(String, Duration)> {
+        let time = self.time.take();
+        let left = time
+            .filter(|_| self.room == scenes.old && !is_menu(scenes.current))
+            .map(|time| (room_key(&self.room, scenes.current), time));
+        self.room.clear();
+        if !is_menu(scenes.current) {
+            self.room.push_str(scenes.current);
+            self.time = Some(Duration::ZERO);
+        }
+        left
+    }
+}
@@ -1,9 +1,8 @@
 use core::mem;
 
-#[cfg(debug_assertions)]
-use alloc::format;
 use alloc::{
     boxed::Box,
+    format,
     string::{String, ToString},
     vec::Vec,
 };
@@ -25,6 +24,8 @@ static SILKSONG_NAMES: [&str; 2] = [
 // const PRE_MENU_INTRO: &str = "Pre_Menu_Intro";
 pub const MENU_TITLE: &str = "Menu_Title";
 pub const QUIT_TO_MENU: &str = "Quit_To_Menu";
+/// The game over screen after a Steel Soul death
+pub const PERMA_DEATH: &str = "PermaDeath";
 
 pub const OPENING_SEQUENCE: &str = "Opening_Sequence";
 pub static OPENING_SCENES: [&str; 1] = [OPENING_SEQUENCE];
@@ -68,6 +69,12 @@ pub const UI_STATE_PAUSED: i32 = 5;
 // HERO_TRANSITION_STATE 2, 3: Waiting to enter, Entering?
 pub const HERO_TRANSITION_STATE_WAITING_TO_ENTER_LEVEL: i32 = 2;
 
+// PERMADEATH_MODE 0: Off
+// PERMADEATH_MODE 1: On, Steel Soul
+pub const PERMADEATH_MODE_ON: i32 = 1;
+// PERMADEATH_MODE 2: Dead, the Steel Soul save is over
+pub const PERMADEATH_MODE_DEAD: i32 = 2;
+
 pub struct StringListOffsets {
     string_len: u64,
     string_contents: u64,
@@ -104,18 +111,67 @@ pub fn is_menu(s: &str) -> bool {
 
 // --------------------------------------------------------
 
+/// Declares a struct of pointers, and a module of constants with the names of its fields.
+/// A pointer can be followed by `=> [...]` with alternate paths,
+/// for fields that have been renamed in other versions of the game.
 macro_rules! declare_pointers {
-    ( $g:ident { $( $f:ident : $t:ty = $e:expr ),*, } ) => {
+    ( $g:ident, $names:ident { $( $f:ident : $t:ty = $e:expr $( => [ $( $alt:expr ),* ] )? ),*, } ) => {
         pub struct $g {
             $( pub $f : $t ),*,
         }
 
+        /// The names of the pointers, as used by `resolve_fields` and `broken`
+        #[allow(non_upper_case_globals)]
+        pub mod $names {
+            $( pub const $f: &str = stringify!($f); )*
+        }
+
         impl $g {
             pub fn new() -> $g {
                 $g {
                     $( $f : $e ),*,
                 }
             }
+
+            /// Switches each pointer that doesn't resolve in this version of the game
+            /// to the first of its alternate paths that does
+            #[allow(unused_variables)]
+            pub fn resolve_alternates(&mut self, mem: &Memory) {
+                $( $(
+                    if !resolves(mem, &self.$f) {
+                        for alt in [ $( $alt ),* ] {
+                            if resolves(mem, &alt) {
+                                asr::print_message(&format!(
+                                    "{}: using an alternate path",
+                                    stringify!($f)
+                                ));
+                                self.$f = alt;
+                                break;
+                            }
+                        }
+                    }
+                )? )*
+            }
+
+            /// Resolves the pointers named in `names`, leaving the rest to resolve on first use
+            pub fn resolve_fields(&self, mem: &Memory, names: &[&str]) {
+                $(
+                    if names.contains(&$names::$f) {
+                        resolves(mem, &self.$f);
+                    }
+                )*
+            }
+
+            /// The names of the pointers named in `names` that don't resolve
+            pub fn broken(&self, mem: &Memory, names: &[&str]) -> Vec<&'static str> {
+                let mut broken = Vec::new();
+                $(
+                    if names.contains(&$names::$f) && !resolves(mem, &self.$f) {
+                        broken.push($names::$f);
+                    }
+                )*
+                broken
+            }
         }
 
         impl Default for $g {
@@ -126,9 +182,12 @@ macro_rules! declare_pointers {
     };
 }
 
-declare_pointers!(GameManagerPointers {
+declare_pointers!(GameManagerPointers, gm_fields {
     scene_name: UnityPointer<2> = UnityPointer::new("GameManager", 0, &["_instance", "sceneName"]),
     next_scene_name: UnityPointer<2> = UnityPointer::new("GameManager", 0, &["_instance", "nextSceneName"]),
+    map_zone_string: UnityPointer<2> = UnityPointer::new("GameManager", 0, &["_instance", "mapZoneString"]),
+    hero_death_count: UnityPointer<2> = UnityPointer::new("GameManager", 0, &["_instance", "heroDeathCount"]),
+    profile_id: UnityPointer<2> = UnityPointer::new("GameManager", 0, &["_instance", "profileID"]),
     entry_gate_name: UnityPointer<2> = UnityPointer::new("GameManager", 0, &["_instance", "entryGateName"]),
     game_state: UnityPointer<2> = UnityPointer::new("GameManager", 0, &["_instance", "<GameState>k__BackingField"]),
     ui_state_vanilla: UnityPointer<3> = UnityPointer::new(
@@ -185,15 +244,49 @@ declare_pointers!(GameManagerPointers {
         0,
         &["_instance", "sceneLoad", "<IsActivationAllowed>k__BackingField"],
     ),
+    is_loading: UnityPointer<2> = UnityPointer::new("GameManager", 0, &["_instance", "isLoading"]),
+    current_load_duration: UnityPointer<2> = UnityPointer::new("GameManager", 0, &["_instance", "currentLoadDuration"]),
+    is_in_scene_transition: UnityPointer<2> = UnityPointer::new("GameManager", 0, &["_instance", "<IsInSceneTransition>k__BackingField"]),
+    is_waiting_for_scene_ready: UnityPointer<1> = UnityPointer::new("GameManager", 0, &["<IsWaitingForSceneReady>k__BackingField"]),
 });
 
-declare_pointers!(PlayerDataPointers {
+declare_pointers!(PlayerDataPointers, pd_fields {
     disable_pause: UnityPointer<3> = UnityPointer::new(
         "GameManager",
         0,
         &["_instance", "playerData", "disablePause"],
     ),
     health: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "health"]),
+    health_blue: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "healthBlue"]),
+    damaged_blue: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "damagedBlue"]),
+    damaged_purple: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "damagedPurple"]),
+    max_health: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "maxHealth"]),
+    silk: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "silk"]),
+    silk_max: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "silkMax"]),
+    geo: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "geo"]),
+    shell_shards: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "ShellShards"]),
+    map_zone: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "mapZone"]),
+    current_area: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "currentArea"]),
+    completion_percentage: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "completionPercentage"]),
+    play_time: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "playTime"]),
+    version: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "version"]),
+    revision_break: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "RevisionBreak"]),
+    profile_id: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "profileID"]),
+    hero_corpse_scene: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "HeroCorpseScene"]),
+    permadeath_mode: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "permadeathMode"]),
+    encountered_moss_mother: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "encounteredMossMother"]),
+    encountered_bell_beast: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "encounteredBellBeast"]),
+    encountered_lace1: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "encounteredLace1"]),
+    encountered_song_golem: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "encounteredSongGolem"]),
+    encountered_dock_foremen: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "encounteredDockForemen"]),
+    encountered_vampire_gnat_boss: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "encounteredVampireGnatBoss"]),
+    encountered_spinner: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "encounteredSpinner"]),
+    encountered_phantom: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "encounteredPhantom"]),
+    encountered_coral_drillers: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "encounteredCoralDrillers"]),
+    encountered_last_judge: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "encounteredLastJudge"]),
+    encountered_trobbio: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "encounteredTrobbio"]),
+    encountered_cogwork_dancers: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "encounteredCogworkDancers"]),
+    encountered_lace_tower: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "encounteredLaceTower"]),
     defeated_moss_mother: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "defeatedMossMother"]),
     has_needle_throw: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "hasNeedleThrow"]),
     defeated_bell_beast: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "defeatedBellBeast"]),
@@ -310,7 +403,9 @@ declare_pointers!(PlayerDataPointers {
     caravan_troupe_location: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "CaravanTroupeLocation"]),
     belltown_relic_dealer_gave_relic: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "BelltownRelicDealerGaveRelic"]),
     collected_ward_key: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "CollectedWardKey"]),
-    belltown_greeter_met_time_passed: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "BelltownGreeterMetTimepassed"]),
+    belltown_greeter_met_time_passed: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "BelltownGreeterMetTimePassed"]) => [
+        UnityPointer::new("GameManager", 0, &["_instance", "playerData", "BelltownGreeterMetTimepassed"])
+    ],
     bell_shrine_enclave: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "bellShrineEnclave"]),
     defeated_zap_core_enemy: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "defeatedZapCoreEnemy"]),
     skull_king_defeated: UnityPointer<3> = UnityPointer::new("GameManager", 0, &["_instance", "playerData", "skullKingDefeated"]),
@@ -394,6 +489,73 @@ impl Memory<'_> {
     }
 }
 
+/// Whether a pointer resolves, reading a single byte so that it works for any field type
+fn resolves<const CAP: usize>(mem: &Memory, p: &UnityPointer<CAP>) -> bool {
+    mem.deref::<u8, CAP>(p).is_ok()
+}
+
+// --------------------------------------------------------
+
+/// The fields that the splits, load removal, and hit counter all need,
+/// read once per tick so that they share one view of the frame
+#[derive(Clone, Debug, Default)]
+pub struct TickSnapshot {
+    pub game_state: i32,
+    pub ui_state: i32,
+    pub scene_name: String,
+    pub next_scene_name: String,
+    pub scene_load_activation_allowed: bool,
+    pub health: Option<i32>,
+    /// Only read when counting hits, false otherwise
+    pub hazard_death: bool,
+    /// Only read when counting hits, false otherwise
+    pub hero_recoil_frozen: bool,
+    /// Only read for the cocoon splits or the death scene
+    pub hero_corpse_scene: Option<String>,
+    /// Only read for the in-game clock or the save slot
+    pub play_time: Option<f32>,
+}
+
+/// Which of the fields of the tick snapshot that only some settings need are read
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TickReads {
+    pub hits: bool,
+    pub hero_corpse_scene: bool,
+    pub play_time: bool,
+}
+
+impl TickSnapshot {
+    pub fn read(
+        mem: &Memory,
+        gm: &GameManagerPointers,
+        pd: &PlayerDataPointers,
+        reads: TickReads,
+    ) -> Self {
+        TickSnapshot {
+            game_state: mem.deref(&gm.game_state).unwrap_or_default(),
+            ui_state: mem.deref(&gm.ui_state_vanilla).unwrap_or_default(),
+            scene_name: mem.read_string(&gm.scene_name).unwrap_or_default(),
+            next_scene_name: mem.read_string(&gm.next_scene_name).unwrap_or_default(),
+            scene_load_activation_allowed: mem
+                .deref(&gm.scene_load_activation_allowed)
+                .unwrap_or_default(),
+            health: mem.deref(&pd.health).ok(),
+            hazard_death: reads.hits && mem.deref(&gm.hazard_death).unwrap_or_default(),
+            hero_recoil_frozen: reads.hits && mem.deref(&gm.hero_recoil_frozen).unwrap_or_default(),
+            hero_corpse_scene: if reads.hero_corpse_scene {
+                mem.read_string(&pd.hero_corpse_scene)
+            } else {
+                None
+            },
+            play_time: if reads.play_time {
+                mem.deref(&pd.play_time).ok()
+            } else {
+                None
+            },
+        }
+    }
+}
+
 // --------------------------------------------------------
 
 pub struct SceneStore {
@@ -404,6 +566,8 @@ pub struct SceneStore {
     new_data_next: bool,
     last_next: bool,
     pub split_this_transition: bool,
+    prev_corpse_scene: String,
+    curr_corpse_scene: String,
 }
 
 impl SceneStore {
@@ -416,9 +580,34 @@ impl SceneStore {
             new_data_next: false,
             last_next: true,
             split_this_transition: false,
+            prev_corpse_scene: "".to_string(),
+            curr_corpse_scene: "".to_string(),
         }
     }
 
+    /// The scene of the hero's cocoon, empty when there is no cocoon,
+    /// as a pair to see when a cocoon is recovered
+    pub fn corpse_pair(&self) -> Pair<&str> {
+        Pair {
+            old: &self.prev_corpse_scene,
+            current: &self.curr_corpse_scene,
+        }
+    }
+
+    pub fn update_corpse_scene(&mut self, tick: &TickSnapshot) {
+        // the player data isn't meaningful in menus,
+        // and the next save loaded can have a different cocoon
+        if is_menu(&self.curr_scene_name) {
+            self.prev_corpse_scene.clear();
+            self.curr_corpse_scene.clear();
+            return;
+        }
+        let Some(hcs) = &tick.hero_corpse_scene else {
+            return;
+        };
+        self.prev_corpse_scene = mem::replace(&mut self.curr_corpse_scene, hcs.clone());
+    }
+
     pub fn pair(&self) -> Pair<&str> {
         if self.last_next && self.next_scene_name != self.curr_scene_name {
             Pair {
@@ -457,13 +646,10 @@ impl SceneStore {
         }
     }
 
-    pub fn transition_now(&mut self, mem: &Memory, gm: &GameManagerPointers) -> bool {
-        self.new_curr_scene_name(mem.read_string(&gm.scene_name).unwrap_or_default());
-        if mem
-            .deref(&gm.scene_load_activation_allowed)
-            .unwrap_or_default()
-        {
-            self.new_next_scene_name(mem.read_string(&gm.next_scene_name).unwrap_or_default());
+    pub fn transition_now(&mut self, tick: &TickSnapshot) -> bool {
+        self.new_curr_scene_name(tick.scene_name.clone());
+        if tick.scene_load_activation_allowed {
+            self.new_next_scene_name(tick.next_scene_name.clone());
         }
 
         if self.new_data_next {
@@ -1,5 +1,10 @@
-use alloc::{vec, vec::Vec};
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
 use asr::{settings::Gui, watcher::Pair};
+use core::fmt;
 use ugly_widget::{
     radio_button::{options_str, RadioButtonOptions},
     store::StoreWidget,
@@ -7,9 +12,9 @@ use ugly_widget::{
 
 use crate::{
     silksong_memory::{
-        is_menu, GameManagerPointers, Memory, PlayerDataPointers, SceneStore,
-        DEATH_RESPAWN_MARKER_INIT, GAME_STATE_PLAYING, MENU_TITLE, NON_MENU_GAME_STATES,
-        OPENING_SCENES,
+        gm_fields, is_menu, pd_fields, GameManagerPointers, Memory, PlayerDataPointers, SceneStore,
+        TickSnapshot, DEATH_RESPAWN_MARKER_INIT, GAME_STATE_PLAYING, MENU_TITLE,
+        NON_MENU_GAME_STATES, OPENING_SCENES,
     },
     timer::{should_split, SplitterAction},
 };
@@ -49,10 +54,45 @@ pub enum Split {
     ///
     /// Splits when player HP is 0
     PlayerDeath,
+    /// Completion 10% (Event)
+    ///
+    /// Splits when the completion percentage reaches 10%
+    Completion10,
+    /// Completion 25% (Event)
+    ///
+    /// Splits when the completion percentage reaches 25%
+    Completion25,
+    /// Completion 50% (Event)
+    ///
+    /// Splits when the completion percentage reaches 50%
+    Completion50,
+    /// Completion 75% (Event)
+    ///
+    /// Splits when the completion percentage reaches 75%
+    Completion75,
+    /// Completion 90% (Event)
+    ///
+    /// Splits when the completion percentage reaches 90%
+    Completion90,
+    /// Completion 100% (Event)
+    ///
+    /// Splits when the completion percentage reaches 100%
+    #[alias = "CompletionThreshold"]
+    Completion100,
+    /// Cocoon Recovered (Event)
+    ///
+    /// Splits when recovering the cocoon left behind after a death
+    CocoonRecovered,
     /// Any Transition (Transition)
     ///
     /// Splits when entering a transition (only one will split per transition)
     AnyTransition,
+    /// Hitless Failed (Misc)
+    ///
+    /// Never splits on its own. When Hitless is set to Failed Split,
+    /// the first hit skips to this split and splits it.
+    /// Put it at the end of the splits
+    HitlessFailed,
     // endregion: Start, End, and Menu
 
     // region: MossLands
@@ -759,11 +799,282 @@ impl StoreWidget for Split {
     }
 }
 
+/// The description of a split, such as `Moss Mother (Boss)`
+pub fn split_description(split: &Split) -> String {
+    let key = options_str(split);
+    Split::radio_button_options()
+        .into_iter()
+        .find(|o| o.key == key)
+        .map(|o| o.description.to_string())
+        .unwrap_or_else(|| key.to_string())
+}
+
+/// A GameManager or PlayerData pointer that a split or a feature reads, by field name
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum SplitPointer {
+    Gm(&'static str),
+    Pd(&'static str),
+}
+
+impl SplitPointer {
+    /// The field name, if it's a GameManager pointer
+    pub fn gm_field(&self) -> Option<&'static str> {
+        match self {
+            SplitPointer::Gm(f) => Some(*f),
+            SplitPointer::Pd(_) => None,
+        }
+    }
+
+    /// The field name, if it's a PlayerData pointer
+    pub fn pd_field(&self) -> Option<&'static str> {
+        match self {
+            SplitPointer::Pd(f) => Some(*f),
+            SplitPointer::Gm(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for SplitPointer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SplitPointer::Gm(field) => write!(f, "gm.{}", field),
+            SplitPointer::Pd(field) => write!(f, "pd.{}", field),
+        }
+    }
+}
+
+/// Read by the splits that check the game state
+const GAME_STATE: SplitPointer = SplitPointer::Gm(gm_fields::game_state);
+/// Read by the splits on transitions, through the scene store
+const SCENE_NAME: SplitPointer = SplitPointer::Gm(gm_fields::scene_name);
+const NEXT_SCENE_NAME: SplitPointer = SplitPointer::Gm(gm_fields::next_scene_name);
+
+/// The pointers that a split reads.
+/// Only the PlayerData pointers among these are resolved and read for the configured splits,
+/// on top of the fields that load removal and the hit counter always need.
+pub fn split_pointers(split: &Split) -> &'static [SplitPointer] {
+    use SplitPointer::{Gm, Pd};
+    match split {
+        Split::ManualSplit => &[GAME_STATE],
+        Split::StartNewGame => &[SCENE_NAME, NEXT_SCENE_NAME],
+        Split::Act1Start => &[
+            SCENE_NAME,
+            NEXT_SCENE_NAME,
+            Gm(gm_fields::entry_gate_name),
+            GAME_STATE,
+            Pd(pd_fields::disable_pause),
+        ],
+        Split::EndingSplit => &[SCENE_NAME, NEXT_SCENE_NAME],
+        Split::EndingA => &[SCENE_NAME, NEXT_SCENE_NAME],
+        Split::Menu => &[SCENE_NAME, NEXT_SCENE_NAME],
+        Split::PlayerDeath => &[GAME_STATE, Pd(pd_fields::health)],
+        Split::Completion10 => &[GAME_STATE, Pd(pd_fields::completion_percentage)],
+        Split::Completion25 => &[GAME_STATE, Pd(pd_fields::completion_percentage)],
+        Split::Completion50 => &[GAME_STATE, Pd(pd_fields::completion_percentage)],
+        Split::Completion75 => &[GAME_STATE, Pd(pd_fields::completion_percentage)],
+        Split::Completion90 => &[GAME_STATE, Pd(pd_fields::completion_percentage)],
+        Split::Completion100 => &[GAME_STATE, Pd(pd_fields::completion_percentage)],
+        Split::CocoonRecovered => &[Pd(pd_fields::hero_corpse_scene)],
+        Split::AnyTransition => &[SCENE_NAME, NEXT_SCENE_NAME],
+        Split::MossMother => &[GAME_STATE, Pd(pd_fields::defeated_moss_mother)],
+        Split::MossMotherTrans => &[
+            SCENE_NAME,
+            NEXT_SCENE_NAME,
+            Pd(pd_fields::defeated_moss_mother),
+        ],
+        Split::SilkSpear => &[GAME_STATE, Pd(pd_fields::has_needle_throw)],
+        Split::SilkSpearTrans => &[SCENE_NAME, NEXT_SCENE_NAME, Pd(pd_fields::has_needle_throw)],
+        Split::BellBeast => &[GAME_STATE, Pd(pd_fields::defeated_bell_beast)],
+        Split::BellBeastTrans => &[
+            SCENE_NAME,
+            NEXT_SCENE_NAME,
+            Pd(pd_fields::defeated_bell_beast),
+        ],
+        Split::MarrowBell => &[GAME_STATE, Pd(pd_fields::bell_shrine_bone_forest)],
+        Split::SwiftStep => &[GAME_STATE, Pd(pd_fields::has_dash)],
+        Split::SwiftStepTrans => &[SCENE_NAME, NEXT_SCENE_NAME, Pd(pd_fields::has_dash)],
+        Split::Lace1 => &[GAME_STATE, Pd(pd_fields::defeated_lace1)],
+        Split::Lace1Trans => &[SCENE_NAME, NEXT_SCENE_NAME, Pd(pd_fields::defeated_lace1)],
+        Split::DeepDocksBell => &[GAME_STATE, Pd(pd_fields::bell_shrine_wilds)],
+        Split::EnterWormways => &[SCENE_NAME, NEXT_SCENE_NAME],
+        Split::EnterFarFields => &[SCENE_NAME, NEXT_SCENE_NAME],
+        Split::DriftersCloak => &[GAME_STATE, Pd(pd_fields::has_brolly)],
+        Split::DriftersCloakTrans => &[SCENE_NAME, NEXT_SCENE_NAME, Pd(pd_fields::has_brolly)],
+        Split::FourthChorus => &[GAME_STATE, Pd(pd_fields::defeated_song_golem)],
+        Split::EnterGreymoor => &[SCENE_NAME, NEXT_SCENE_NAME],
+        Split::GreymoorBell => &[GAME_STATE, Pd(pd_fields::bell_shrine_greymoor)],
+        Split::Moorwing => &[GAME_STATE, Pd(pd_fields::defeated_vampire_gnat_boss)],
+        Split::MoorwingTrans => &[
+            SCENE_NAME,
+            NEXT_SCENE_NAME,
+            Pd(pd_fields::defeated_vampire_gnat_boss),
+        ],
+        Split::EnterShellwood => &[SCENE_NAME, NEXT_SCENE_NAME],
+        Split::ClingGrip => &[GAME_STATE, Pd(pd_fields::has_wall_jump)],
+        Split::ClingGripTrans => &[SCENE_NAME, NEXT_SCENE_NAME, Pd(pd_fields::has_wall_jump)],
+        Split::ShellwoodBell => &[GAME_STATE, Pd(pd_fields::bell_shrine_shellwood)],
+        Split::EnterBellhart => &[SCENE_NAME, NEXT_SCENE_NAME],
+        Split::Widow => &[GAME_STATE, Pd(pd_fields::spinner_defeated)],
+        Split::BellhartBell => &[GAME_STATE, Pd(pd_fields::bell_shrine_bellhart)],
+        Split::LastJudge => &[GAME_STATE, Pd(pd_fields::defeated_last_judge)],
+        Split::EnterMist => &[SCENE_NAME, NEXT_SCENE_NAME],
+        Split::LeaveMist => &[SCENE_NAME, NEXT_SCENE_NAME],
+        Split::Phantom => &[GAME_STATE, Pd(pd_fields::defeated_phantom)],
+        Split::Act2Started => &[GAME_STATE, Pd(pd_fields::act2_started)],
+        Split::CogworkDancers => &[GAME_STATE, Pd(pd_fields::defeated_cogwork_dancers)],
+        Split::WhisperingVaultsArena => {
+            &[GAME_STATE, Pd(pd_fields::completed_library_entry_battle)]
+        }
+        Split::Trobbio => &[GAME_STATE, Pd(pd_fields::defeated_trobbio)],
+        Split::TrobbioTrans => &[SCENE_NAME, NEXT_SCENE_NAME, Pd(pd_fields::defeated_trobbio)],
+        Split::Clawline => &[GAME_STATE, Pd(pd_fields::has_harpoon_dash)],
+        Split::EnterHighHalls => &[SCENE_NAME, NEXT_SCENE_NAME],
+        Split::EnterHighHallsArena => &[SCENE_NAME, NEXT_SCENE_NAME],
+        Split::HighHallsArena => &[GAME_STATE, Pd(pd_fields::hang04_battle)],
+        Split::Lace2 => &[GAME_STATE, Pd(pd_fields::defeated_lace_tower)],
+        Split::VaultkeepersMelody => &[GAME_STATE, Pd(pd_fields::has_melody_librarian)],
+        Split::VaultkeepersMelodyTrans => &[
+            SCENE_NAME,
+            NEXT_SCENE_NAME,
+            Pd(pd_fields::has_melody_librarian),
+        ],
+        Split::ArchitectsMelody => &[GAME_STATE, Pd(pd_fields::has_melody_architect)],
+        Split::ArchitectsMelodyTrans => &[
+            SCENE_NAME,
+            NEXT_SCENE_NAME,
+            Pd(pd_fields::has_melody_architect),
+        ],
+        Split::ConductorsMelody => &[GAME_STATE, Pd(pd_fields::has_melody_conductor)],
+        Split::ConductorsMelodyTrans => &[
+            SCENE_NAME,
+            NEXT_SCENE_NAME,
+            Pd(pd_fields::has_melody_conductor),
+        ],
+        Split::UnlockedMelodyLift => &[GAME_STATE, Pd(pd_fields::unlocked_melody_lift)],
+        Split::NeedleUpgrade1 => &[GAME_STATE, Pd(pd_fields::nail_upgrades)],
+        Split::NeedleUpgrade2 => &[GAME_STATE, Pd(pd_fields::nail_upgrades)],
+        Split::NeedleUpgrade3 => &[GAME_STATE, Pd(pd_fields::nail_upgrades)],
+        Split::NeedleUpgrade4 => &[GAME_STATE, Pd(pd_fields::nail_upgrades)],
+        Split::ReaperCrestTrans => &[
+            SCENE_NAME,
+            NEXT_SCENE_NAME,
+            GAME_STATE,
+            Pd(pd_fields::completed_memory_reaper),
+        ],
+        Split::SavedFleaHuntersMarch => &[GAME_STATE, Pd(pd_fields::savedflea_ant_03)],
+        Split::SavedFleaBellhart => &[GAME_STATE, Pd(pd_fields::savedflea_belltown_04)],
+        Split::SavedFleaMarrow => &[GAME_STATE, Pd(pd_fields::savedflea_bone_06)],
+        Split::SavedFleaDeepDocksSprint => &[GAME_STATE, Pd(pd_fields::savedflea_bone_east_05)],
+        Split::SavedFleaFarFieldsPilgrimsRest => {
+            &[GAME_STATE, Pd(pd_fields::savedflea_bone_east_10_church)]
+        }
+        Split::SavedFleaFarFieldsTrap => &[GAME_STATE, Pd(pd_fields::savedflea_bone_east_17b)],
+        Split::SavedFleaSandsOfKarak => &[GAME_STATE, Pd(pd_fields::savedflea_coral_24)],
+        Split::SavedFleaBlastedSteps => &[GAME_STATE, Pd(pd_fields::savedflea_coral_35)],
+        Split::SavedFleaWormways => &[GAME_STATE, Pd(pd_fields::savedflea_crawl_06)],
+        Split::SavedFleaDeepDocksArena => &[GAME_STATE, Pd(pd_fields::savedflea_dock_03d)],
+        Split::SavedFleaDeepDocksBellway => &[GAME_STATE, Pd(pd_fields::savedflea_dock_16)],
+        Split::SavedFleaBilewaterOrgan => &[GAME_STATE, Pd(pd_fields::savedflea_dust_09)],
+        Split::SavedFleaSinnersRoad => &[GAME_STATE, Pd(pd_fields::savedflea_dust_12)],
+        Split::SavedFleaGreymoorRoof => &[GAME_STATE, Pd(pd_fields::savedflea_greymoor_06)],
+        Split::SavedFleaGreymoorLake => &[GAME_STATE, Pd(pd_fields::savedflea_greymoor_15b)],
+        Split::SavedFleaWhisperingVaults => &[GAME_STATE, Pd(pd_fields::savedflea_library_01)],
+        Split::SavedFleaSongclave => &[GAME_STATE, Pd(pd_fields::savedflea_library_09)],
+        Split::SavedFleaMountFay => &[GAME_STATE, Pd(pd_fields::savedflea_peak_05c)],
+        Split::SavedFleaBilewaterTrap => &[GAME_STATE, Pd(pd_fields::savedflea_shadow_10)],
+        Split::SavedFleaBilewaterThieves => &[GAME_STATE, Pd(pd_fields::savedflea_shadow_28)],
+        Split::SavedFleaShellwood => &[GAME_STATE, Pd(pd_fields::savedflea_shellwood_03)],
+        Split::SavedFleaSlabBellway => &[GAME_STATE, Pd(pd_fields::savedflea_slab_06)],
+        Split::SavedFleaSlabCage => &[GAME_STATE, Pd(pd_fields::savedflea_slab_cell)],
+        Split::SavedFleaChoralChambersWind => &[GAME_STATE, Pd(pd_fields::savedflea_song_11)],
+        Split::SavedFleaChoralChambersCage => &[GAME_STATE, Pd(pd_fields::savedflea_song_14)],
+        Split::SavedFleaUnderworksCauldron => &[GAME_STATE, Pd(pd_fields::savedflea_under_21)],
+        Split::SavedFleaUnderworksWispThicket => &[GAME_STATE, Pd(pd_fields::savedflea_under_23)],
+        Split::SavedFleaGiantFlea => &[GAME_STATE, Pd(pd_fields::tamed_giant_flea)],
+        Split::SavedFleaVog => &[GAME_STATE, Pd(pd_fields::met_troupe_hunter_wild)],
+        Split::SavedFleaKratt => &[GAME_STATE, Pd(pd_fields::caravan_lech_saved)],
+        Split::PutrifiedDuctsStation => &[GAME_STATE, Pd(pd_fields::unlocked_aqueduct_station)],
+        Split::BellhartStation => &[GAME_STATE, Pd(pd_fields::unlocked_belltown_station)],
+        Split::FarFieldsStation => &[GAME_STATE, Pd(pd_fields::unlocked_boneforest_east_station)],
+        Split::GrandBellwayStation => &[GAME_STATE, Pd(pd_fields::unlocked_city_station)],
+        Split::BlastedStepsStation => &[GAME_STATE, Pd(pd_fields::unlocked_coral_tower_station)],
+        Split::DeepDocksStation => &[GAME_STATE, Pd(pd_fields::unlocked_docks_station)],
+        Split::GreymoorStation => &[GAME_STATE, Pd(pd_fields::unlocked_greymoor_station)],
+        Split::SlabStation => &[GAME_STATE, Pd(pd_fields::unlocked_peak_station)],
+        Split::BilewaterStation => &[GAME_STATE, Pd(pd_fields::unlocked_shadow_station)],
+        Split::ShellwoodStation => &[GAME_STATE, Pd(pd_fields::unlocked_shellwood_station)],
+        Split::ChoralChambersTube => &[GAME_STATE, Pd(pd_fields::unlocked_song_tube)],
+        Split::UnderworksTube => &[GAME_STATE, Pd(pd_fields::unlocked_under_tube)],
+        Split::GrandBellwayTube => &[GAME_STATE, Pd(pd_fields::unlocked_city_bellway_tube)],
+        Split::HighHallsTube => &[GAME_STATE, Pd(pd_fields::unlocked_hang_tube)],
+        Split::SongclaveTube => &[GAME_STATE, Pd(pd_fields::unlocked_enclave_tube)],
+        Split::MemoriumTube => &[GAME_STATE, Pd(pd_fields::unlocked_arborium_tube)],
+        Split::SeenShakraBonebottom => &[GAME_STATE, Pd(pd_fields::seen_mapper_bonetown)],
+        Split::SeenShakraMarrow => &[GAME_STATE, Pd(pd_fields::seen_mapper_bone_forest)],
+        Split::SeenShakraDeepDocks => &[GAME_STATE, Pd(pd_fields::seen_mapper_docks)],
+        Split::SeenShakraFarFields => &[GAME_STATE, Pd(pd_fields::seen_mapper_wilds)],
+        Split::SeenShakraWormways => &[GAME_STATE, Pd(pd_fields::seen_mapper_crawl)],
+        Split::SeenShakraGreymoor => &[GAME_STATE, Pd(pd_fields::seen_mapper_greymoor)],
+        Split::SeenShakraBellhart => &[GAME_STATE, Pd(pd_fields::seen_mapper_bellhart)],
+        Split::SeenShakraShellwood => &[GAME_STATE, Pd(pd_fields::seen_mapper_shellwood)],
+        Split::SeenShakraHuntersMarch => &[GAME_STATE, Pd(pd_fields::seen_mapper_hunters_nest)],
+        Split::SeenShakraBlastedSteps => &[GAME_STATE, Pd(pd_fields::seen_mapper_judge_steps)],
+        Split::SeenShakraSinnersRoad => &[GAME_STATE, Pd(pd_fields::seen_mapper_dustpens)],
+        Split::SeenShakraMountFay => &[GAME_STATE, Pd(pd_fields::seen_mapper_peak)],
+        Split::SeenShakraBilewater => &[GAME_STATE, Pd(pd_fields::seen_mapper_shadow)],
+        Split::SeenShakraSandsOfKarak => &[GAME_STATE, Pd(pd_fields::seen_mapper_coral_caverns)],
+        Split::MetJubilanaEnclave => &[GAME_STATE, Pd(pd_fields::met_city_merchant_enclave)],
+        Split::MetShermaEnclave => &[GAME_STATE, Pd(pd_fields::met_sherma_enclave)],
+        Split::UnlockedPrinceCage => &[GAME_STATE, Pd(pd_fields::unlocked_dust_cage)],
+        Split::GreenPrinceInVerdania => &[GAME_STATE, Pd(pd_fields::green_prince_location)],
+        Split::SeenFleatopiaEmpty => &[GAME_STATE, Pd(pd_fields::seen_fleatopia_empty)],
+        Split::FaydownCloak => &[GAME_STATE, Pd(pd_fields::has_double_jump)],
+        Split::SilkSoar => &[GAME_STATE, Pd(pd_fields::has_super_jump)],
+        Split::HeartNyleth => &[GAME_STATE, Pd(pd_fields::collected_heart_flower)],
+        Split::HeartKhann => &[GAME_STATE, Pd(pd_fields::collected_heart_coral)],
+        Split::HeartKarmelita => &[GAME_STATE, Pd(pd_fields::collected_heart_hunter)],
+        Split::HeartClover => &[GAME_STATE, Pd(pd_fields::collected_heart_clover)],
+        Split::RedMemory => &[GAME_STATE, Pd(pd_fields::completed_red_memory)],
+        Split::BellhouseKeyConversation => {
+            &[GAME_STATE, Pd(pd_fields::belltown_greeter_house_full_dlg)]
+        }
+        Split::VerdaniaOrbsCollected => &[
+            GAME_STATE,
+            Pd(pd_fields::clover_memory_orbs_collected_target),
+        ],
+        Split::Forebrothers => &[GAME_STATE, Pd(pd_fields::defeated_dock_foremen)],
+        Split::Groal => &[GAME_STATE, Pd(pd_fields::defeated_swamp_shaman)],
+        Split::Conchflies1 => &[GAME_STATE, Pd(pd_fields::defeated_coral_drillers)],
+        Split::SavageBeastfly1 => &[GAME_STATE, Pd(pd_fields::defeated_bone_flyer_giant)],
+        Split::SavageBeastfly2 => &[
+            GAME_STATE,
+            Pd(pd_fields::defeated_bone_flyer_giant_golem_scene),
+        ],
+        Split::CaravanTroupeGreymoor => &[GAME_STATE, Pd(pd_fields::caravan_troupe_location)],
+        Split::CaravanTroupeFleatopia => &[GAME_STATE, Pd(pd_fields::caravan_troupe_location)],
+        Split::SoldRelic => &[GAME_STATE, Pd(pd_fields::belltown_relic_dealer_gave_relic)],
+        Split::CollectedWhiteWardKey => &[GAME_STATE, Pd(pd_fields::collected_ward_key)],
+        Split::PavoTimePassed => &[GAME_STATE, Pd(pd_fields::belltown_greeter_met_time_passed)],
+        Split::SongclaveBell => &[GAME_STATE, Pd(pd_fields::bell_shrine_enclave)],
+        Split::Voltvyrm => &[GAME_STATE, Pd(pd_fields::defeated_zap_core_enemy)],
+        Split::SkullTyrant1 => &[GAME_STATE, Pd(pd_fields::skull_king_defeated)],
+        Split::ShermaReturned => &[GAME_STATE, Pd(pd_fields::sherma_healer_active)],
+        Split::JubilanaRescuedMemorium => &[GAME_STATE, Pd(pd_fields::enclave_merchant_saved)],
+        Split::JubilanaRescuedChoralChambers => &[GAME_STATE, Pd(pd_fields::city_merchant_saved)],
+        Split::SilkAndSoulOffered => &[GAME_STATE, Pd(pd_fields::caretaker_offered_snare_quest)],
+        Split::SoulSnareReady => &[GAME_STATE, Pd(pd_fields::soul_snare_ready)],
+        Split::Seth => &[GAME_STATE, Pd(pd_fields::defeated_seth)],
+        Split::AbyssEscape => &[GAME_STATE, Pd(pd_fields::completed_abyss_ascent)],
+        Split::BallowMoved => &[GAME_STATE, Pd(pd_fields::ballow_moved_to_diving_bell)],
+        Split::Act3Started => &[GAME_STATE, Pd(pd_fields::black_thread_world)],
+        Split::HitlessFailed => &[],
+    }
+}
+
 pub fn menu_splits(
     split: &Split,
     scenes: &Pair<&str>,
     _mem: &Memory,
-    _gm: &GameManagerPointers,
     _pd: &PlayerDataPointers,
 ) -> SplitterAction {
     match split {
@@ -780,7 +1091,6 @@ pub fn transition_splits(
     split: &Split,
     scenes: &Pair<&str>,
     mem: &Memory,
-    _gm: &GameManagerPointers,
     pd: &PlayerDataPointers,
 ) -> SplitterAction {
     match split {
@@ -900,6 +1210,7 @@ pub fn transition_once_splits(
     mem: &Memory,
     gm: &GameManagerPointers,
     pd: &PlayerDataPointers,
+    tick: &TickSnapshot,
 ) -> SplitterAction {
     match split {
         // region: Start, End, and Menu
@@ -910,9 +1221,7 @@ pub fn transition_once_splits(
                         && mem.read_string(&gm.entry_gate_name).unwrap_or_default()
                             == DEATH_RESPAWN_MARKER_INIT))
                 && mem.deref(&pd.disable_pause).is_ok_and(|d: bool| !d)
-                && mem
-                    .deref(&gm.game_state)
-                    .is_ok_and(|s: i32| s == GAME_STATE_PLAYING),
+                && tick.game_state == GAME_STATE_PLAYING,
         ),
 
         // else
@@ -920,20 +1229,48 @@ pub fn transition_once_splits(
     }
 }
 
+pub fn corpse_splits(split: &Split, corpse: &Pair<&str>) -> SplitterAction {
+    match split {
+        // region: Start, End, and Menu
+        Split::CocoonRecovered => should_split(!corpse.old.is_empty() && corpse.current.is_empty()),
+        // endregion: Start, End, and Menu
+
+        // else
+        _ => should_split(false),
+    }
+}
+
 pub fn continuous_splits(
     split: &Split,
     mem: &Memory,
-    gm: &GameManagerPointers,
     pd: &PlayerDataPointers,
+    tick: &TickSnapshot,
 ) -> SplitterAction {
-    let game_state: i32 = mem.deref(&gm.game_state).unwrap_or_default();
-    if !NON_MENU_GAME_STATES.contains(&game_state) {
+    if !NON_MENU_GAME_STATES.contains(&tick.game_state) {
         return should_split(false);
     }
     match split {
         // region: Start, End, and Menu
         Split::ManualSplit => SplitterAction::ManualSplit,
-        Split::PlayerDeath => should_split(mem.deref(&pd.health).is_ok_and(|h: i32| h == 0)),
+        Split::PlayerDeath => should_split(tick.health == Some(0)),
+        Split::Completion10 => should_split(
+            mem.deref(&pd.completion_percentage).is_ok_and(|c: f32| c >= 10.0),
+        ),
+        Split::Completion25 => should_split(
+            mem.deref(&pd.completion_percentage).is_ok_and(|c: f32| c >= 25.0),
+        ),
+        Split::Completion50 => should_split(
+            mem.deref(&pd.completion_percentage).is_ok_and(|c: f32| c >= 50.0),
+        ),
+        Split::Completion75 => should_split(
+            mem.deref(&pd.completion_percentage).is_ok_and(|c: f32| c >= 75.0),
+        ),
+        Split::Completion90 => should_split(
+            mem.deref(&pd.completion_percentage).is_ok_and(|c: f32| c >= 90.0),
+        ),
+        Split::Completion100 => should_split(
+            mem.deref(&pd.completion_percentage).is_ok_and(|c: f32| c >= 100.0),
+        ),
         // endregion: Start, End, and Menu
 
         // region: MossLands
@@ -1361,33 +1698,37 @@ pub fn continuous_splits(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn splits(
     split: &Split,
     mem: &Memory,
     gm: &GameManagerPointers,
     pd: &PlayerDataPointers,
+    tick: &TickSnapshot,
     trans_now: bool,
     ss: &mut SceneStore,
 ) -> SplitterAction {
-    let a1 = continuous_splits(split, mem, gm, pd).or_else(|| {
-        let scenes = ss.pair();
-        let a2 = if !ss.split_this_transition {
-            transition_once_splits(split, &scenes, mem, gm, pd)
-        } else {
-            SplitterAction::Pass
-        };
-        a2.or_else(|| {
-            if trans_now {
-                if is_menu(scenes.old) || is_menu(scenes.current) {
-                    menu_splits(split, &scenes, mem, gm, pd)
-                } else {
-                    transition_splits(split, &scenes, mem, gm, pd)
-                }
+    let a1 = corpse_splits(split, &ss.corpse_pair())
+        .or_else(|| continuous_splits(split, mem, pd, tick))
+        .or_else(|| {
+            let scenes = ss.pair();
+            let a2 = if !ss.split_this_transition {
+                transition_once_splits(split, &scenes, mem, gm, pd, tick)
             } else {
                 SplitterAction::Pass
-            }
-        })
-    });
+            };
+            a2.or_else(|| {
+                if trans_now {
+                    if is_menu(scenes.old) || is_menu(scenes.current) {
+                        menu_splits(split, &scenes, mem, pd)
+                    } else {
+                        transition_splits(split, &scenes, mem, pd)
+                    }
+                } else {
+                    SplitterAction::Pass
+                }
+            })
+        });
     if a1 != SplitterAction::Pass {
         ss.split_this_transition = true;
     }
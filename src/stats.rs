@@ -0,0 +1,105 @@
+use alloc::format;
+
+use crate::silksong_memory::{pd_fields, Memory, PlayerDataPointers};
+
+// --------------------------------------------------------
+
+/// A player stat that can be shown as a timer variable
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PlayerStat {
+    Health,
+    MaxHealth,
+    Silk,
+    MaxSilk,
+    Rosaries,
+    ShellShards,
+}
+
+impl PlayerStat {
+    pub const ALL: [PlayerStat; 6] = [
+        PlayerStat::Health,
+        PlayerStat::MaxHealth,
+        PlayerStat::Silk,
+        PlayerStat::MaxSilk,
+        PlayerStat::Rosaries,
+        PlayerStat::ShellShards,
+    ];
+
+    pub fn variable(self) -> &'static str {
+        match self {
+            PlayerStat::Health => "health",
+            PlayerStat::MaxHealth => "max health",
+            PlayerStat::Silk => "silk",
+            PlayerStat::MaxSilk => "max silk",
+            PlayerStat::Rosaries => "rosaries",
+            PlayerStat::ShellShards => "shell shards",
+        }
+    }
+
+    /// The name of the field the stat is read from, for the diagnostics
+    pub fn field(self) -> &'static str {
+        match self {
+            PlayerStat::Health => pd_fields::health,
+            PlayerStat::MaxHealth => pd_fields::max_health,
+            PlayerStat::Silk => pd_fields::silk,
+            PlayerStat::MaxSilk => pd_fields::silk_max,
+            PlayerStat::Rosaries => pd_fields::geo,
+            PlayerStat::ShellShards => pd_fields::shell_shards,
+        }
+    }
+
+    pub fn read(self, mem: &Memory, pd: &PlayerDataPointers) -> Option<i32> {
+        match self {
+            PlayerStat::Health => mem.deref(&pd.health).ok(),
+            PlayerStat::MaxHealth => mem.deref(&pd.max_health).ok(),
+            PlayerStat::Silk => mem.deref(&pd.silk).ok(),
+            PlayerStat::MaxSilk => mem.deref(&pd.silk_max).ok(),
+            PlayerStat::Rosaries => mem.deref(&pd.geo).ok(),
+            PlayerStat::ShellShards => mem.deref(&pd.shell_shards).ok(),
+        }
+    }
+}
+
+/// The last values set for the player stat variables,
+/// so that variables are only set when they change
+#[derive(Debug, Default)]
+pub struct StatVariables {
+    last: [Option<i32>; PlayerStat::ALL.len()],
+    last_completion: Option<f32>,
+}
+
+impl StatVariables {
+    /// Sets the variable for `stat` if `value` changed
+    pub fn update(&mut self, stat: PlayerStat, value: i32) {
+        let last = &mut self.last[stat as usize];
+        if *last != Some(value) {
+            asr::timer::set_variable_int(stat.variable(), value);
+            *last = Some(value);
+        }
+    }
+
+    /// Clears the variable for `stat`, if it was set
+    pub fn clear(&mut self, stat: PlayerStat) {
+        let last = &mut self.last[stat as usize];
+        if last.is_some() {
+            asr::timer::set_variable(stat.variable(), crate::DASH);
+            *last = None;
+        }
+    }
+
+    /// Sets the completion variable if `completion` changed
+    pub fn update_completion(&mut self, completion: f32) {
+        if self.last_completion != Some(completion) {
+            asr::timer::set_variable("completion", &format!("{:.1}%", completion));
+            self.last_completion = Some(completion);
+        }
+    }
+
+    /// Clears the completion variable, if it was set
+    pub fn clear_completion(&mut self) {
+        if self.last_completion.is_some() {
+            asr::timer::set_variable("completion", crate::DASH);
+            self.last_completion = None;
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use asr::settings::Gui;
+use ugly_widget::{
+    radio_button::{options_str, RadioButtonOptions},
+    store::StoreWidget,
+};
+
+use crate::silksong_memory::{PERMADEATH_MODE_DEAD, PERMADEATH_MODE_ON, PERMA_DEATH};
+
+// --------------------------------------------------------
+
+#[derive(Clone, Debug, Default, Eq, Gui, Ord, PartialEq, PartialOrd, RadioButtonOptions)]
+pub enum SteelSoulDeath {
+    /// End the run
+    ///
+    /// Skips to the last split and splits it on the death, without saving its hits as comparisons
+    #[default]
+    EndRun,
+    /// Reset
+    ///
+    /// Resets the timer on the death
+    Reset,
+    /// Keep timing to the game over screen
+    ///
+    /// Skips to the last split and splits it when the game over screen appears, without saving its hits as comparisons
+    KeepTiming,
+    /// Ignore
+    ///
+    /// Doesn't check for Steel Soul deaths
+    Ignore,
+}
+
+impl StoreWidget for SteelSoulDeath {
+    fn insert_into(&self, settings_map: &asr::settings::Map, key: &str) -> bool {
+        let new_s = options_str(self);
+        if settings_map
+            .get(key)
+            .is_some_and(|old_v| old_v.get_string().is_some_and(|old_s| old_s == new_s))
+        {
+            return false;
+        }
+        settings_map.insert(key, new_s);
+        true
+    }
+}
+
+/// Whether the scene is the game over screen after a Steel Soul death
+pub fn is_game_over(scene: &str) -> bool {
+    scene == PERMA_DEATH
+}
+
+/// Whether a Steel Soul save just died,
+/// either from a death while in Steel Soul mode, or from the mode becoming dead
+pub fn steel_soul_died(last_mode: i32, mode: i32, died: bool) -> bool {
+    (died && (mode == PERMADEATH_MODE_ON || mode == PERMADEATH_MODE_DEAD))
+        || (last_mode == PERMADEATH_MODE_ON && mode == PERMADEATH_MODE_DEAD)
+}
@@ -1,3 +1,47 @@
+use asr::settings::Gui;
+use ugly_widget::{
+    radio_button::{options_str, RadioButtonOptions},
+    store::StoreWidget,
+};
+
+#[derive(Clone, Debug, Default, Eq, Gui, Ord, PartialEq, PartialOrd, RadioButtonOptions)]
+pub enum TimerMode {
+    /// Splits
+    ///
+    /// Starts, splits, and resets according to the splits
+    #[default]
+    Splits,
+    /// Room Timer
+    ///
+    /// Splits on every transition, and times each room in game time, with loads removed
+    RoomTimer,
+    /// IL Practice
+    ///
+    /// Starts on entering the IL start room, splits according to the splits after the first,
+    /// and resets on death, on going to the menu, or on going from the start room
+    /// back to the room it was entered from. Other exits from the start room don't reset
+    IlPractice,
+    /// Boss Practice
+    ///
+    /// Starts on encountering the practice boss or entering its arena, ends on defeating it,
+    /// and resets on death or on leaving the arena
+    BossPractice,
+}
+
+impl StoreWidget for TimerMode {
+    fn insert_into(&self, settings_map: &asr::settings::Map, key: &str) -> bool {
+        let new_s = options_str(self);
+        if settings_map
+            .get(key)
+            .is_some_and(|old_v| old_v.get_string().is_some_and(|old_s| old_s == new_s))
+        {
+            return false;
+        }
+        settings_map.insert(key, new_s);
+        true
+    }
+}
+
 #[derive(Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
 #[allow(dead_code)]
 pub enum SplitterAction {
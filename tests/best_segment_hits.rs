@@ -0,0 +1,34 @@
+// cargo test --target $(rustc -vV | sed -n 's|host: ||p')
+
+use silksong_autosplit_wasm::hits::{sum_of_best_hits, update_best_segment_hits};
+
+/// Splits of a run with 4 segments
+const SPLITS_LEN: usize = 5;
+
+#[test]
+fn reset_after_some_splits_has_no_sum_of_best() {
+    let mut best = Vec::new();
+    // reset after 2 of the 4 segments
+    update_best_segment_hits(&mut best, &[Some(1), Some(0)]);
+    assert_eq!(best, [Some(1), Some(0)]);
+    assert_eq!(sum_of_best_hits(&best, SPLITS_LEN), None);
+
+    // a full run later fills in the rest
+    update_best_segment_hits(&mut best, &[Some(2), Some(1), Some(3), Some(0)]);
+    assert_eq!(best, [Some(1), Some(0), Some(3), Some(0)]);
+    assert_eq!(sum_of_best_hits(&best, SPLITS_LEN), Some(4));
+}
+
+#[test]
+fn skipped_segment_has_no_sum_of_best() {
+    let mut best = Vec::new();
+    update_best_segment_hits(&mut best, &[Some(1), None, None, Some(2)]);
+    assert_eq!(sum_of_best_hits(&best, SPLITS_LEN), None);
+}
+
+#[test]
+fn more_bests_than_segments_has_no_sum_of_best() {
+    let best = [Some(1), Some(0), Some(3), Some(0), Some(2)];
+    assert_eq!(sum_of_best_hits(&best, SPLITS_LEN), None);
+    assert_eq!(sum_of_best_hits(&[], 1), None);
+}
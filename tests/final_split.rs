@@ -0,0 +1,142 @@
+// cargo test --target $(rustc -vV | sed -n 's|host: ||p')
+
+use silksong_autosplit_wasm::hits::{
+    end_segments, end_split_index, undo_end_split_index, undo_to, HitCause, HitCounts,
+};
+
+/// The segments in the run, split by splits 1 to 3 after the start split 0
+const SEGMENTS: u64 = 3;
+
+/// The hit bookkeeping of a run, following the steps in `lib.rs`
+#[derive(Debug)]
+struct Run {
+    split_index: Option<u64>,
+    segments_splitted: Vec<bool>,
+    segment_hits: Vec<HitCounts>,
+    cumulative_hits: Vec<HitCounts>,
+    hits: HitCounts,
+    split_made: bool,
+}
+
+impl Run {
+    /// As in `start_run`
+    fn start() -> Run {
+        Run {
+            split_index: Some(0),
+            segments_splitted: Vec::new(),
+            segment_hits: vec![HitCounts::default()],
+            cumulative_hits: Vec::new(),
+            hits: HitCounts::default(),
+            split_made: false,
+        }
+    }
+
+    fn hit(&mut self) {
+        self.hits.add_hit(HitCause::Enemy);
+        let i = self.split_index.unwrap() as usize;
+        self.segment_hits[i].add_hit(HitCause::Enemy);
+    }
+
+    /// A split made by the auto splitter, as in `split_segment`
+    fn split(&mut self) {
+        let new_i = self.split_index.unwrap() as usize + 1;
+        self.split_index = Some(new_i as u64);
+        self.split_made = true;
+        self.segments_splitted.push(true);
+        self.segment_hits.push(HitCounts::default());
+        self.cumulative_hits.resize(new_i, self.hits);
+    }
+
+    /// The timer ended, as in the End arm of `AutoSplitterState::update`,
+    /// with the timer's split index when it's known
+    fn end(&mut self, timer_index: Option<u64>) {
+        let split_made = std::mem::take(&mut self.split_made);
+        let end_index = end_split_index(self.split_index, timer_index, split_made);
+        self.split_index = Some(end_index);
+        end_segments(&mut self.segments_splitted, end_index as usize);
+        self.cumulative_hits.resize(end_index as usize, self.hits);
+    }
+
+    /// The final split was undone, as in the Undo arm of `AutoSplitterState::update`,
+    /// with the timer's split index when it's known
+    fn undo_end(&mut self, timer_index: Option<u64>) {
+        let new_i = undo_end_split_index(self.split_index, timer_index) as usize;
+        self.split_index = Some(new_i as u64);
+        undo_to(
+            &mut self.segments_splitted,
+            &mut self.segment_hits,
+            &mut self.cumulative_hits,
+            new_i,
+        );
+    }
+
+    /// Checks that the run is back before the final split, with the hits where they were,
+    /// so that the final split can split again
+    fn assert_before_final_split(&self) {
+        assert_eq!(self.split_index, Some(SEGMENTS - 1));
+        assert_eq!(self.segments_splitted, [true, true]);
+        assert_eq!(self.cumulative_hits.len(), 2);
+        assert_eq!(
+            self.segment_hits
+                .iter()
+                .map(HitCounts::total)
+                .collect::<Vec<_>>(),
+            [0, 1, 1]
+        );
+    }
+}
+
+/// Splits the first 2 segments, with a hit in the second and third
+fn run_to_final_split() -> Run {
+    let mut run = Run::start();
+    run.split();
+    run.hit();
+    run.split();
+    run.hit();
+    run.split_made = false;
+    run
+}
+
+#[test]
+fn stable_undo_after_the_auto_splitter_ended_the_run() {
+    let mut run = run_to_final_split();
+    run.split();
+    run.end(None);
+    assert_eq!(run.split_index, Some(SEGMENTS));
+    assert_eq!(run.segments_splitted, [true, true, true]);
+    assert_eq!(run.cumulative_hits.len(), SEGMENTS as usize);
+    run.undo_end(None);
+    run.assert_before_final_split();
+}
+
+#[test]
+fn stable_undo_after_the_timer_ended_the_run() {
+    let mut run = run_to_final_split();
+    run.end(None);
+    assert_eq!(run.split_index, Some(SEGMENTS));
+    assert_eq!(run.segments_splitted, [true, true, true]);
+    assert_eq!(run.cumulative_hits.len(), SEGMENTS as usize);
+    run.undo_end(None);
+    run.assert_before_final_split();
+}
+
+#[test]
+fn unstable_undo_after_the_auto_splitter_ended_the_run() {
+    let mut run = run_to_final_split();
+    run.split();
+    run.end(Some(SEGMENTS));
+    assert_eq!(run.split_index, Some(SEGMENTS));
+    assert_eq!(run.segments_splitted, [true, true, true]);
+    run.undo_end(Some(SEGMENTS - 1));
+    run.assert_before_final_split();
+}
+
+#[test]
+fn unstable_undo_after_the_timer_ended_the_run() {
+    let mut run = run_to_final_split();
+    run.end(Some(SEGMENTS));
+    assert_eq!(run.split_index, Some(SEGMENTS));
+    assert_eq!(run.segments_splitted, [true, true, true]);
+    run.undo_end(Some(SEGMENTS - 1));
+    run.assert_before_final_split();
+}
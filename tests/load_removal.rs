@@ -0,0 +1,384 @@
+// cargo test --target $(rustc -vV | sed -n 's|host: ||p')
+
+use silksong_autosplit_wasm::{
+    load_removal::{
+        self, LoadCause, LoadRemovalRules, LoadRemovalSnapshot, LoadRemovalState, LoadSignal,
+    },
+    silksong_memory::{
+        GAME_STATE_CUTSCENE, GAME_STATE_ENTERING_LEVEL, GAME_STATE_EXITING_LEVEL,
+        GAME_STATE_LOADING, GAME_STATE_MAIN_MENU, GAME_STATE_PLAYING,
+        HERO_TRANSITION_STATE_WAITING_TO_ENTER_LEVEL, MENU_TITLE, QUIT_TO_MENU, UI_STATE_PAUSED,
+        UI_STATE_PLAYING,
+    },
+};
+
+const UI_STATE_MAIN_MENU: i32 = 1;
+const HERO_TRANSITION_STATE_NONE: i32 = 0;
+const HERO_TRANSITION_STATE_EXITING: i32 = 1;
+const HERO_TRANSITION_STATE_ENTERING: i32 = 3;
+
+fn tick(
+    game_state: i32,
+    ui_state: i32,
+    scene_name: &str,
+    next_scene: &str,
+    accepting_input: bool,
+    hero_transition_state: i32,
+) -> LoadRemovalSnapshot {
+    LoadRemovalSnapshot {
+        game_state,
+        ui_state,
+        scene_name: scene_name.to_string(),
+        next_scene: next_scene.to_string(),
+        accepting_input,
+        hero_transition_state,
+        ..Default::default()
+    }
+}
+
+fn playing(scene_name: &str) -> LoadRemovalSnapshot {
+    tick(
+        GAME_STATE_PLAYING,
+        UI_STATE_PLAYING,
+        scene_name,
+        scene_name,
+        true,
+        HERO_TRANSITION_STATE_NONE,
+    )
+}
+
+/// Runs a sequence of ticks from a fresh state,
+/// returning the cause of each tick, `None` when not paused
+fn run(rules: LoadRemovalRules, ticks: &[LoadRemovalSnapshot]) -> Vec<Option<LoadCause>> {
+    let mut state = LoadRemovalState::new();
+    ticks
+        .iter()
+        .map(|t| {
+            let d = load_removal::load_removal_step(&rules, &LoadSignal::Heuristic, &mut state, t);
+            assert_eq!(d.is_game_time_paused, d.cause.is_some());
+            d.cause
+        })
+        .collect()
+}
+
+// The sequences below are written by hand, following the states the game goes through,
+// not recorded from the game
+
+fn door_transition() -> Vec<LoadRemovalSnapshot> {
+    let mut exiting = tick(
+        GAME_STATE_EXITING_LEVEL,
+        UI_STATE_PLAYING,
+        "Bone_05",
+        "Bone_04",
+        false,
+        HERO_TRANSITION_STATE_EXITING,
+    );
+    let mut activating = exiting.clone();
+    activating.scene_load_activation_allowed = true;
+    exiting.accepting_input = true;
+    vec![
+        playing("Bone_05"),
+        exiting,
+        activating,
+        tick(
+            GAME_STATE_LOADING,
+            UI_STATE_PLAYING,
+            "Bone_04",
+            "Bone_04",
+            false,
+            HERO_TRANSITION_STATE_EXITING,
+        ),
+        tick(
+            GAME_STATE_ENTERING_LEVEL,
+            UI_STATE_PLAYING,
+            "Bone_04",
+            "Bone_04",
+            false,
+            HERO_TRANSITION_STATE_WAITING_TO_ENTER_LEVEL,
+        ),
+        tick(
+            GAME_STATE_PLAYING,
+            UI_STATE_PLAYING,
+            "Bone_04",
+            "Bone_04",
+            false,
+            HERO_TRANSITION_STATE_ENTERING,
+        ),
+        playing("Bone_04"),
+    ]
+}
+
+fn menu_quit_and_continue() -> Vec<LoadRemovalSnapshot> {
+    vec![
+        playing("Bone_04"),
+        tick(
+            GAME_STATE_PLAYING,
+            UI_STATE_PAUSED,
+            "Bone_04",
+            "Bone_04",
+            true,
+            HERO_TRANSITION_STATE_NONE,
+        ),
+        tick(
+            GAME_STATE_EXITING_LEVEL,
+            UI_STATE_PAUSED,
+            QUIT_TO_MENU,
+            MENU_TITLE,
+            false,
+            HERO_TRANSITION_STATE_NONE,
+        ),
+        tick(
+            GAME_STATE_MAIN_MENU,
+            UI_STATE_MAIN_MENU,
+            MENU_TITLE,
+            MENU_TITLE,
+            true,
+            HERO_TRANSITION_STATE_NONE,
+        ),
+        tick(
+            GAME_STATE_PLAYING,
+            UI_STATE_MAIN_MENU,
+            MENU_TITLE,
+            MENU_TITLE,
+            false,
+            HERO_TRANSITION_STATE_NONE,
+        ),
+        tick(
+            GAME_STATE_PLAYING,
+            UI_STATE_PLAYING,
+            MENU_TITLE,
+            "Bone_04",
+            false,
+            HERO_TRANSITION_STATE_NONE,
+        ),
+        tick(
+            GAME_STATE_LOADING,
+            UI_STATE_PLAYING,
+            "Bone_04",
+            "Bone_04",
+            false,
+            HERO_TRANSITION_STATE_NONE,
+        ),
+        tick(
+            GAME_STATE_ENTERING_LEVEL,
+            UI_STATE_PLAYING,
+            "Bone_04",
+            "Bone_04",
+            false,
+            HERO_TRANSITION_STATE_WAITING_TO_ENTER_LEVEL,
+        ),
+        playing("Bone_04"),
+    ]
+}
+
+fn death_and_respawn() -> Vec<LoadRemovalSnapshot> {
+    let mut respawn_exiting = tick(
+        GAME_STATE_EXITING_LEVEL,
+        UI_STATE_PLAYING,
+        "Bone_04",
+        "Bonetown",
+        false,
+        HERO_TRANSITION_STATE_NONE,
+    );
+    respawn_exiting.scene_load_activation_allowed = true;
+    vec![
+        playing("Bone_04"),
+        tick(
+            GAME_STATE_PLAYING,
+            UI_STATE_PLAYING,
+            "Bone_04",
+            "Bone_04",
+            false,
+            HERO_TRANSITION_STATE_NONE,
+        ),
+        tick(
+            GAME_STATE_CUTSCENE,
+            UI_STATE_PLAYING,
+            "Bone_04",
+            "Bone_04",
+            false,
+            HERO_TRANSITION_STATE_NONE,
+        ),
+        respawn_exiting,
+        tick(
+            GAME_STATE_LOADING,
+            UI_STATE_PLAYING,
+            "Bonetown",
+            "Bonetown",
+            false,
+            HERO_TRANSITION_STATE_NONE,
+        ),
+        playing("Bonetown"),
+    ]
+}
+
+#[test]
+fn door_transition_pauses_only_during_the_load() {
+    use LoadCause::SceneLoad;
+    let expected = vec![
+        None,
+        None,
+        Some(SceneLoad),
+        Some(SceneLoad),
+        Some(SceneLoad),
+        None,
+        None,
+    ];
+    assert_eq!(
+        run(LoadRemovalRules::Standard, &door_transition()),
+        expected
+    );
+    assert_eq!(run(LoadRemovalRules::Strict, &door_transition()), expected);
+}
+
+#[test]
+fn menu_quit_pauses_for_pause_menu_quit_and_teleport() {
+    use LoadCause::{Menu, SceneLoad, Teleport};
+    assert_eq!(
+        run(LoadRemovalRules::Standard, &menu_quit_and_continue()),
+        vec![
+            None,
+            Some(Menu),
+            Some(Menu),
+            None,
+            Some(Teleport),
+            Some(Teleport),
+            Some(SceneLoad),
+            Some(SceneLoad),
+            None,
+        ]
+    );
+}
+
+#[test]
+fn strict_counts_the_pause_menu() {
+    use LoadCause::{Menu, SceneLoad, Teleport};
+    assert_eq!(
+        run(LoadRemovalRules::Strict, &menu_quit_and_continue()),
+        vec![
+            None,
+            None,
+            Some(Menu),
+            None,
+            Some(Teleport),
+            Some(Teleport),
+            Some(SceneLoad),
+            Some(SceneLoad),
+            None,
+        ]
+    );
+}
+
+#[test]
+fn death_pauses_only_during_the_respawn_load() {
+    use LoadCause::SceneLoad;
+    assert_eq!(
+        run(LoadRemovalRules::Standard, &death_and_respawn()),
+        vec![None, None, None, Some(SceneLoad), Some(SceneLoad), None]
+    );
+}
+
+#[test]
+fn rta_never_pauses() {
+    for ticks in [
+        door_transition(),
+        menu_quit_and_continue(),
+        death_and_respawn(),
+    ] {
+        assert!(run(LoadRemovalRules::Rta, &ticks)
+            .iter()
+            .all(Option::is_none));
+    }
+}
+
+#[test]
+fn loading_flags_replace_the_heuristic() {
+    let mut state = LoadRemovalState::new();
+    let mut t = playing("Bone_04");
+    t.is_loading = true;
+    let d = load_removal::load_removal_step(
+        &LoadRemovalRules::Standard,
+        &LoadSignal::LoadingFlags,
+        &mut state,
+        &t,
+    );
+    assert!(d.is_game_time_paused);
+    assert!(d.flags_loading);
+    assert!(!d.heuristic_loading);
+
+    let mut t = playing("Bone_04");
+    t.hero_transition_state = HERO_TRANSITION_STATE_WAITING_TO_ENTER_LEVEL;
+    let d = load_removal::load_removal_step(
+        &LoadRemovalRules::Standard,
+        &LoadSignal::LoadingFlags,
+        &mut state,
+        &t,
+    );
+    assert!(!d.is_game_time_paused);
+    assert!(d.heuristic_loading);
+}
+
+#[test]
+fn hazard_respawn_cause() {
+    let mut t = tick(
+        GAME_STATE_ENTERING_LEVEL,
+        UI_STATE_PLAYING,
+        "Bone_04",
+        "Bone_04",
+        false,
+        HERO_TRANSITION_STATE_WAITING_TO_ENTER_LEVEL,
+    );
+    t.hazard_respawning = true;
+    assert_eq!(
+        run(LoadRemovalRules::Standard, &[t]),
+        vec![Some(LoadCause::HazardRespawn)]
+    );
+}
+
+#[test]
+fn clauses() {
+    assert!(load_removal::teleporting(true));
+    assert!(load_removal::ui_not_playing(
+        GAME_STATE_PLAYING,
+        UI_STATE_PAUSED
+    ));
+    assert!(!load_removal::ui_not_playing(
+        GAME_STATE_LOADING,
+        UI_STATE_PAUSED
+    ));
+    assert!(load_removal::pause_menu(
+        GAME_STATE_ENTERING_LEVEL,
+        UI_STATE_PAUSED
+    ));
+    assert!(!load_removal::pause_menu(
+        GAME_STATE_PLAYING,
+        UI_STATE_PLAYING
+    ));
+    assert!(load_removal::input_blocked(GAME_STATE_LOADING, false));
+    assert!(!load_removal::input_blocked(GAME_STATE_CUTSCENE, false));
+    assert!(load_removal::scene_loading(GAME_STATE_EXITING_LEVEL, true));
+    assert!(!load_removal::scene_loading(
+        GAME_STATE_EXITING_LEVEL,
+        false
+    ));
+    assert!(load_removal::waiting_to_enter_level(
+        HERO_TRANSITION_STATE_WAITING_TO_ENTER_LEVEL
+    ));
+    assert!(load_removal::loading_flags(false, true, true));
+    assert!(!load_removal::loading_flags(false, false, true));
+    assert!(load_removal::menu_loading(
+        UI_STATE_PAUSED,
+        QUIT_TO_MENU,
+        MENU_TITLE
+    ));
+    assert!(!load_removal::menu_loading(
+        UI_STATE_PLAYING,
+        QUIT_TO_MENU,
+        MENU_TITLE
+    ));
+    assert!(!load_removal::menu_loading(
+        UI_STATE_MAIN_MENU,
+        MENU_TITLE,
+        MENU_TITLE
+    ));
+}
@@ -0,0 +1,103 @@
+// cargo test --target $(rustc -vV | sed -n 's|host: ||p')
+
+use silksong_autosplit_wasm::hits::{remap_cumulative, remap_segments, remap_splits};
+
+fn splits(names: &[&str]) -> Vec<String> {
+    names.iter().map(|n| n.to_string()).collect()
+}
+
+const OLD: &[&str] = &[
+    "StartNewGame",
+    "MossMother",
+    "BellBeast",
+    "Lace1",
+    "EndingA",
+];
+
+/// Cumulative comparisons at the end of each old split after the first
+const CUMULATIVE: &[Option<i64>] = &[Some(1), Some(3), Some(4), Some(6)];
+
+/// Segment comparisons between each pair of old splits
+const SEGMENTS: &[Option<i64>] = &[Some(1), Some(2), Some(1), Some(2)];
+
+#[test]
+fn unchanged_splits_keep_everything() {
+    let map = remap_splits(&splits(OLD), &splits(OLD));
+    assert_eq!(map, [Some(0), Some(1), Some(2), Some(3), Some(4)]);
+    assert_eq!(remap_cumulative(CUMULATIVE, &map), CUMULATIVE);
+    assert_eq!(remap_segments(SEGMENTS, &map), SEGMENTS);
+}
+
+#[test]
+fn inserted_split_keeps_the_comparisons_after_it() {
+    let new = [
+        "StartNewGame",
+        "MossMother",
+        "Moorwing",
+        "BellBeast",
+        "Lace1",
+        "EndingA",
+    ];
+    let map = remap_splits(&splits(OLD), &splits(&new));
+    assert_eq!(map, [Some(0), Some(1), None, Some(2), Some(3), Some(4)]);
+    assert_eq!(
+        remap_cumulative(CUMULATIVE, &map),
+        [Some(1), None, Some(3), Some(4), Some(6)]
+    );
+    assert_eq!(
+        remap_segments(SEGMENTS, &map),
+        [Some(1), None, None, Some(1), Some(2)]
+    );
+}
+
+#[test]
+fn removed_split_keeps_the_comparisons_after_it() {
+    let new = ["StartNewGame", "MossMother", "Lace1", "EndingA"];
+    let map = remap_splits(&splits(OLD), &splits(&new));
+    assert_eq!(map, [Some(0), Some(1), Some(3), Some(4)]);
+    assert_eq!(
+        remap_cumulative(CUMULATIVE, &map),
+        [Some(1), Some(4), Some(6)]
+    );
+    assert_eq!(remap_segments(SEGMENTS, &map), [Some(1), None, Some(2)]);
+}
+
+#[test]
+fn swapped_splits_only_lose_the_comparisons_between_them() {
+    let new = [
+        "StartNewGame",
+        "BellBeast",
+        "MossMother",
+        "Lace1",
+        "EndingA",
+    ];
+    let map = remap_splits(&splits(OLD), &splits(&new));
+    assert_eq!(map, [Some(0), Some(2), Some(1), Some(3), Some(4)]);
+    assert_eq!(
+        remap_cumulative(CUMULATIVE, &map),
+        [None, None, Some(4), Some(6)]
+    );
+    assert_eq!(remap_segments(SEGMENTS, &map), [None, None, None, Some(2)]);
+}
+
+#[test]
+fn changed_first_split_discards_everything() {
+    let new = [
+        "StartNewGameSteelSoul",
+        "MossMother",
+        "BellBeast",
+        "Lace1",
+        "EndingA",
+    ];
+    let map = remap_splits(&splits(OLD), &splits(&new));
+    assert_eq!(map, [None; 5]);
+    assert_eq!(remap_cumulative(CUMULATIVE, &map), [None; 4]);
+    assert_eq!(remap_segments(SEGMENTS, &map), [None; 4]);
+}
+
+#[test]
+fn holes_stay_holes() {
+    let cumulative = [Some(1), None, Some(4), Some(6)];
+    let map = remap_splits(&splits(OLD), &splits(OLD));
+    assert_eq!(remap_cumulative(&cumulative, &map), cumulative);
+}